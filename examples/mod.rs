@@ -23,31 +23,31 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
             Err(e) => {
                 eprintln!("Failed to run basic example: {}", e);
-                Err(e.into())
+                Err(e)
             }
         },
         Some("rag") => {
-            contextual_rag::run_example()?;
+            contextual_rag::run_example().await?;
             println!("RAG example completed successfully");
             Ok(())
         }
         Some("embeddings") => {
-            embeddings_example::run_example()?;
+            embeddings_example::run_example().await?;
             println!("Embeddings example completed successfully");
             Ok(())
         }
         Some("rerank") => {
-            rerank_example::run_example()?;
+            rerank_example::run_example().await?;
             println!("Rerank example completed successfully");
             Ok(())
         }
         Some("scores") => {
-            rerank_with_scores::run_example()?;
+            rerank_with_scores::run_example().await?;
             println!("Scores example completed successfully");
             Ok(())
         }
         Some("advanced") => {
-            advanced_usage::run_example()?;
+            advanced_usage::run_example().await?;
             println!("Advanced example completed successfully");
             Ok(())
         }
@@ -1,8 +1,7 @@
 use voyageai::VoyageAiClient;
 
-#[tokio::main]
 pub async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
-    let client = VoyageAiClient::new();
+    let client = VoyageAiClient::new()?;
 
     let texts = [
         "The quick brown fox jumps over the lazy dog",
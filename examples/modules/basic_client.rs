@@ -4,7 +4,7 @@ use voyageai::{builder::embeddings::EmbeddingsRequestBuilder, VoyageAiClient};
 
 pub async fn run_example() -> Result<(Vec<f32>, Vec<f32>), Box<dyn std::error::Error>> {
     // Build the client
-    let client = VoyageAiClient::new();
+    let client = VoyageAiClient::new()?;
 
     // Embeddings request
     let embeddings_request = EmbeddingsRequestBuilder::new()
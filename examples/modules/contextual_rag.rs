@@ -3,12 +3,11 @@ use voyageai::{
     RerankModel, RerankRequest, VoyageAiClient, VoyageConfig,
 };
 
-#[tokio::main]
 pub async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize client
     let api_key = std::env::var("VOYAGE_API_KEY").expect("VOYAGE_API_KEY must be set");
     let config = VoyageConfig::new(api_key);
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     // Example usage in a Retrieval-Augmented Generation context
     let query = "Explain the benefits of using Rust.";
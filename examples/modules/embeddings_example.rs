@@ -3,10 +3,9 @@ use voyageai::{
     VoyageAiClient, VoyageConfig,
 };
 
-#[tokio::main]
 pub async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
     let config = VoyageConfig::new("your_api_key_here".to_string());
-    let mut client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     let inputs = [
         "The quick brown fox jumps over the lazy dog.",
@@ -20,7 +19,7 @@ pub async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
         .build()
         .expect("Failed to build embeddings request");
 
-    let response = embeddings_request.send().await?;
+    let response = client.create_embeddings(embeddings_request.input).await?;
     for (i, embedding) in response.data.iter().enumerate() {
         println!("Embedding for '{}': {:?}", inputs[i], embedding.embedding);
     }
@@ -1,8 +1,7 @@
 use voyageai::{RerankModel, RerankRequestBuilder, VoyageAiClient};
 
-#[tokio::main]
 pub async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
-    let client = VoyageAiClient::new();
+    let client = VoyageAiClient::new()?;
 
     let rerank_request = RerankRequestBuilder::new()
         .query("What is Rust?")
@@ -1,10 +1,7 @@
 use voyageai::{RerankModel, RerankRequest, VoyageAiClient};
 
-#[tokio::main]
 pub async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
-    let client = VoyageAiClient::with_key(
-        std::env::var("VOYAGEAI_API_KEY").expect("Missing VOYAGEAI_API_KEY environment variable"),
-    );
+    let client = VoyageAiClient::new()?;
 
     let query = "benefits of using Rust for system programming";
     let documents = [
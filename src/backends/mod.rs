@@ -0,0 +1,36 @@
+//! Embedding backends for providers other than Voyage.
+//!
+//! A backend implements [`EmbeddingsClient`](crate::client::EmbeddingsClient), and so
+//! [`Embedder`](crate::traits::llm::Embedder) through `Arc`, with its own model name and
+//! dimension. Plug one into a [`VoyageAiClient`](crate::VoyageAiClient) with
+//! [`VoyageBuilder::with_embedding_backend`](crate::VoyageBuilder::with_embedding_backend)
+//! to run the same search and caching code against it; reranking still uses Voyage.
+//!
+//! ```no_run
+//! # fn run() -> Result<(), voyageai::VoyageError> {
+//! use voyageai::backends::{EmbeddingBackend, OpenAiCompatibleConfig};
+//! use voyageai::VoyageBuilder;
+//!
+//! let openai =
+//!     OpenAiCompatibleConfig::new("https://api.openai.com/v1", "text-embedding-3-small", 1536)
+//!         .with_api_key(std::env::var("OPENAI_API_KEY").unwrap_or_default());
+//! let client = VoyageBuilder::new()
+//!     .with_embedding_backend(EmbeddingBackend::OpenAiCompatible(openai))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod openai;
+
+pub use openai::{OpenAiCompatibleBackend, OpenAiCompatibleConfig, DEFAULT_MAX_BATCH_SIZE};
+
+/// Which service embeds text for a [`VoyageAiClient`](crate::VoyageAiClient).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum EmbeddingBackend {
+    /// The Voyage embeddings API.
+    #[default]
+    Voyage,
+    /// An OpenAI-compatible `/v1/embeddings` endpoint.
+    OpenAiCompatible(OpenAiCompatibleConfig),
+}
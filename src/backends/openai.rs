@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::client::client_limiter::Endpoint;
+use crate::client::embeddings_client::{EmbeddingClient, EmbeddingsClient};
+use crate::client::metrics::ClientMetrics;
+use crate::client::telemetry::OperationSpan;
+use crate::client::transport::{error_for_status, HttpTransport};
+use crate::client::RateLimiter;
+use crate::config::{ApiKey, VoyageConfig};
+use crate::errors::VoyageError;
+use crate::models::embeddings::{
+    EmbeddingData, EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse,
+    InputType, Usage,
+};
+use crate::models::metadata::ResponseMetadata;
+
+/// Inputs sent per request unless configured otherwise.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 128;
+
+/// Endpoint, credentials and model of an OpenAI-compatible embeddings server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenAiCompatibleConfig {
+    /// Base URL including the version, e.g. `https://api.openai.com/v1`. Requests go to
+    /// `<base_url>/embeddings`.
+    pub base_url: String,
+    /// Sent as a bearer token; leave empty for servers without authentication.
+    pub api_key: ApiKey,
+    /// Model name sent with every request.
+    pub model: String,
+    /// Length of the model's vectors.
+    pub dimension: usize,
+    /// Send `dimensions` so the server shortens vectors to `dimension`, for models that
+    /// support it such as OpenAI's `text-embedding-3-*`.
+    pub send_dimensions: bool,
+    /// Prepended to texts embedded as queries, for models that expect an instruction
+    /// (e.g. `"query: "` for E5).
+    pub query_prefix: Option<String>,
+    /// Prepended to texts embedded as documents (e.g. `"passage: "` for E5).
+    pub document_prefix: Option<String>,
+    /// Largest number of inputs per request; larger batches are split.
+    pub max_batch_size: usize,
+}
+
+impl OpenAiCompatibleConfig {
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>, dimension: usize) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key: ApiKey::default(),
+            model: model.into(),
+            dimension,
+            send_dimensions: false,
+            query_prefix: None,
+            document_prefix: None,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        }
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<ApiKey>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    pub fn with_send_dimensions(mut self, send_dimensions: bool) -> Self {
+        self.send_dimensions = send_dimensions;
+        self
+    }
+
+    pub fn with_prefixes(
+        mut self,
+        query_prefix: impl Into<String>,
+        document_prefix: impl Into<String>,
+    ) -> Self {
+        self.query_prefix = Some(query_prefix.into());
+        self.document_prefix = Some(document_prefix.into());
+        self
+    }
+
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+}
+
+/// Embeddings from an OpenAI-compatible `/embeddings` endpoint, such as OpenAI itself or
+/// a self-hosted server (vLLM, Text Embeddings Inference, Ollama, ...).
+///
+/// Requests go through the same transport as the Voyage clients, so timeouts, retries,
+/// rate limits, interceptors and metrics apply. The Voyage `model` of an
+/// [`EmbeddingsRequest`] is ignored in favour of the configured model.
+#[derive(Debug, Clone)]
+pub struct OpenAiCompatibleBackend {
+    transport: HttpTransport,
+    config: OpenAiCompatibleConfig,
+}
+
+#[derive(Serialize)]
+struct OpenAiRequest<'a> {
+    input: &'a [String],
+    model: &'a str,
+    encoding_format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dimensions: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponse {
+    data: Vec<OpenAiEmbedding>,
+    /// Some self-hosted servers leave usage out.
+    #[serde(default)]
+    usage: Usage,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+impl OpenAiCompatibleBackend {
+    /// Creates a backend with default transport settings.
+    pub fn new(config: OpenAiCompatibleConfig) -> Result<Self, VoyageError> {
+        let base = VoyageConfig::new(config.api_key.clone());
+        Self::with_settings_from(config, &base)
+    }
+
+    /// Creates a backend that takes its timeouts, retries, network, compression, rate
+    /// limits, interceptors and metrics from `base`, and its URL and key from `config`.
+    ///
+    /// Interceptors, circuit listeners and metrics are shared with `base`, so they also
+    /// see this backend's requests. The cassette, input deduplication and splitting
+    /// belong to the Voyage embeddings client and are not carried over. The cache is not
+    /// either; [`VoyageAiClient`](crate::VoyageAiClient) puts it in front of the backend.
+    ///
+    /// Fails with `VoyageError::ConfigError` if the HTTP client cannot be built.
+    pub fn with_settings_from(
+        config: OpenAiCompatibleConfig,
+        base: &VoyageConfig,
+    ) -> Result<Self, VoyageError> {
+        debug!(
+            "Creating OpenAI-compatible backend for {} at {}",
+            config.model, config.base_url
+        );
+        let mut transport_config = base.clone();
+        transport_config.api_key = config.api_key.clone();
+        transport_config.key_pool = None;
+        transport_config.base_url = config.base_url.clone();
+        transport_config.cassette = None;
+        transport_config.dedupe_inputs = false;
+        transport_config.split_long_inputs = None;
+        let rate_limiter = Arc::new(RateLimiter::with_limits(base.rate_limits));
+        Ok(Self {
+            transport: HttpTransport::new(transport_config, rate_limiter)?,
+            config,
+        })
+    }
+
+    pub fn config(&self) -> &OpenAiCompatibleConfig {
+        &self.config
+    }
+
+    /// Returns the traffic counters for this backend.
+    pub fn metrics(&self) -> &ClientMetrics {
+        self.transport.metrics()
+    }
+
+    /// Applies the configured prefix for `input_type` to `texts`.
+    fn prefixed(&self, texts: Vec<String>, input_type: Option<InputType>) -> Vec<String> {
+        let prefix = match input_type {
+            Some(InputType::Query) => self.config.query_prefix.as_deref(),
+            Some(InputType::Document) => self.config.document_prefix.as_deref(),
+            None => None,
+        };
+        match prefix {
+            Some(prefix) => texts
+                .into_iter()
+                .map(|t| format!("{}{}", prefix, t))
+                .collect(),
+            None => texts,
+        }
+    }
+
+    /// Embeds one batch, returning its vectors in input order.
+    async fn embed_chunk(
+        &self,
+        texts: &[String],
+        metadata: &mut ResponseMetadata,
+    ) -> Result<(Vec<Vec<f32>>, u32), VoyageError> {
+        let body = OpenAiRequest {
+            input: texts,
+            model: &self.config.model,
+            encoding_format: "float",
+            dimensions: self.config.send_dimensions.then_some(self.config.dimension),
+        };
+        let estimated_tokens = EmbeddingClient::estimate_text_tokens(texts);
+        let (response, key, call_metadata) = self
+            .transport
+            .call(Endpoint::Embeddings, &body, estimated_tokens)
+            .await?;
+        *metadata = call_metadata;
+        if !response.status.is_success() {
+            warn!(
+                "OpenAI-compatible embedding request failed with status: {}",
+                response.status
+            );
+            return Err(error_for_status(&response));
+        }
+
+        let mut parsed: OpenAiResponse = serde_json::from_str(&response.body)?;
+        if parsed.data.len() != texts.len() {
+            return Err(VoyageError::JsonError(format!(
+                "Expected {} embeddings, got {}",
+                texts.len(),
+                parsed.data.len()
+            )));
+        }
+        parsed.data.sort_by_key(|d| d.index);
+        key.limiter()
+            .update_usage(Endpoint::Embeddings, parsed.usage.total_tokens)
+            .await;
+        let embeddings = parsed.data.into_iter().map(|d| d.embedding).collect();
+        Ok((embeddings, parsed.usage.total_tokens))
+    }
+
+    async fn embed_all(
+        &self,
+        texts: &[String],
+    ) -> Result<(Vec<Vec<f32>>, u32, ResponseMetadata), VoyageError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        let mut total_tokens = 0;
+        let mut metadata = ResponseMetadata::default();
+        for chunk in texts.chunks(self.config.max_batch_size.max(1)) {
+            let (chunk_embeddings, tokens) = self.embed_chunk(chunk, &mut metadata).await?;
+            embeddings.extend(chunk_embeddings);
+            total_tokens += tokens;
+        }
+        Ok((embeddings, total_tokens, metadata))
+    }
+}
+
+#[async_trait]
+impl EmbeddingsClient for OpenAiCompatibleBackend {
+    /// Not meaningful for this backend, whose model is [`OpenAiCompatibleConfig::model`];
+    /// requests built by generic code carry this placeholder, which is ignored.
+    fn embedding_model(&self) -> EmbeddingModel {
+        EmbeddingModel::default()
+    }
+
+    fn embedding_model_name(&self) -> &str {
+        &self.config.model
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.config.dimension
+    }
+
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        let texts = match &request.input {
+            EmbeddingsInput::Single(text) => vec![text.clone()],
+            EmbeddingsInput::Multiple(texts) => texts.clone(),
+        };
+        let texts = self.prefixed(texts, request.input_type);
+
+        let span = OperationSpan::embeddings(
+            &self.config.model,
+            texts.len(),
+            EmbeddingClient::estimate_text_tokens(&texts),
+        );
+        let result = span.instrument(self.embed_all(&texts)).await;
+        span.finish(&result);
+        let (embeddings, total_tokens, metadata) = result?;
+
+        Ok(EmbeddingsResponse {
+            object: "list".to_string(),
+            data: embeddings
+                .into_iter()
+                .enumerate()
+                .map(|(index, embedding)| EmbeddingData {
+                    object: "embedding".to_string(),
+                    embedding,
+                    index,
+                })
+                .collect(),
+            model: self.config.model.clone(),
+            usage: Usage { total_tokens },
+            dedupe: None,
+            split: Vec::new(),
+            served_by: None,
+            metadata,
+        })
+    }
+}
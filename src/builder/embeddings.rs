@@ -1,16 +1,24 @@
 use crate::{
     errors::VoyageBuilderError,
-    models::embeddings::{EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, EncodingFormat, InputType},
+    models::embeddings::{
+        EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, EncodingFormat, InputType,
+    },
+    models::usage::estimate_tokens,
 };
 use log::{debug, error};
 
+/// Most texts a single embeddings request may carry.
+const MAX_INPUTS: usize = 128;
+
 #[derive(Debug, Default, Clone)]
 pub struct EmbeddingsRequestBuilder {
     input: Option<EmbeddingsInput>,
     model: Option<EmbeddingModel>,
     input_type: Option<InputType>,
-    truncation: Option<bool>, 
+    truncation: Option<bool>,
     encoding_format: Option<EncodingFormat>,
+    skip_token_checks: bool,
+    split_long_inputs: bool,
 }
 
 impl EmbeddingsRequestBuilder {
@@ -79,6 +87,35 @@ impl EmbeddingsRequestBuilder {
         self
     }
 
+    /// Declares that the request goes to a client that splits long inputs (see
+    /// [`VoyageConfig::split_long_inputs`](crate::config::VoyageConfig)), so texts longer
+    /// than the context length are accepted even with truncation disabled.
+    pub fn split_long_inputs(mut self, split: bool) -> Self {
+        debug!(
+            "Setting split_long_inputs for EmbeddingsRequestBuilder: {}",
+            split
+        );
+        self.split_long_inputs = split;
+        self
+    }
+
+    /// Turns the token count checks of [`build`](Self::build) off or back on. The counts
+    /// are estimates that overshoot for some texts; without the checks, requests over the
+    /// limits are left for the API to reject.
+    pub fn check_token_limits(mut self, check: bool) -> Self {
+        debug!(
+            "Setting token limit checks for EmbeddingsRequestBuilder: {}",
+            check
+        );
+        self.skip_token_checks = !check;
+        self
+    }
+
+    /// Builds the request, rejecting ones the API would refuse: no or more than 128
+    /// inputs, empty texts, more tokens than the model accepts per request and, with
+    /// truncation disabled and no splitting, texts longer than the context length. Token counts
+    /// are estimates (see [`estimate_tokens`]) and their checks can be turned off with
+    /// [`check_token_limits`](Self::check_token_limits).
     pub fn build(self) -> Result<EmbeddingsRequest, VoyageBuilderError> {
         debug!("Building EmbeddingsRequest");
         let input = self.input.ok_or_else(|| {
@@ -89,6 +126,9 @@ impl EmbeddingsRequestBuilder {
             error!("Model is required for EmbeddingsRequest");
             VoyageBuilderError::MissingModel
         })?;
+        // Inputs the client splits reach the API in windows that fit the context length.
+        let shortened = self.truncation.unwrap_or(true) || self.split_long_inputs;
+        validate_input(&input, model, shortened, !self.skip_token_checks)?;
 
         Ok(EmbeddingsRequest {
             input,
@@ -99,3 +139,53 @@ impl EmbeddingsRequestBuilder {
         })
     }
 }
+
+fn validate_input(
+    input: &EmbeddingsInput,
+    model: EmbeddingModel,
+    shortened: bool,
+    check_tokens: bool,
+) -> Result<(), VoyageBuilderError> {
+    let texts = match input {
+        EmbeddingsInput::Single(text) => std::slice::from_ref(text),
+        EmbeddingsInput::Multiple(texts) => texts.as_slice(),
+    };
+    if texts.is_empty() {
+        return Err(VoyageBuilderError::MissingInput);
+    }
+    if texts.len() > MAX_INPUTS {
+        error!("{} inputs exceed the limit of {}", texts.len(), MAX_INPUTS);
+        return Err(VoyageBuilderError::InputListTooLong);
+    }
+
+    let context_length = model.max_context_length();
+    let mut total = 0;
+    for (index, text) in texts.iter().enumerate() {
+        if text.is_empty() {
+            return Err(VoyageBuilderError::EmptyInput(index));
+        }
+        if !check_tokens {
+            continue;
+        }
+        let tokens = estimate_tokens(text);
+        if tokens > context_length {
+            if !shortened {
+                return Err(VoyageBuilderError::InputTooLong {
+                    index,
+                    tokens,
+                    limit: context_length,
+                });
+            }
+            // The API truncates the text, or the client splits it, to the context length.
+            total += context_length;
+        } else {
+            total += tokens;
+        }
+    }
+
+    let limit = model.max_tokens_per_request();
+    if check_tokens && total > limit {
+        return Err(VoyageBuilderError::TokenLimitExceeded(total, limit));
+    }
+    Ok(())
+}
@@ -6,6 +6,7 @@ pub struct RerankRequestBuilder {
     documents: Option<Vec<String>>,
     model: Option<RerankModel>,
     top_k: Option<usize>,
+    truncation: Option<bool>,
 }
 
 impl Default for RerankRequestBuilder {
@@ -21,6 +22,7 @@ impl RerankRequestBuilder {
             documents: None,
             model: None,
             top_k: None,
+            truncation: None,
         }
     }
 
@@ -44,6 +46,11 @@ impl RerankRequestBuilder {
         self
     }
 
+    pub fn truncation(mut self, truncation: bool) -> Self {
+        self.truncation = Some(truncation);
+        self
+    }
+
     pub fn build(self) -> Result<RerankRequest, &'static str> {
         let query = self.query.ok_or("Query is required")?;
         let documents = self.documents.ok_or("Documents are required")?;
@@ -54,6 +61,7 @@ impl RerankRequestBuilder {
             documents,
             model,
             top_k: self.top_k,
+            truncation: self.truncation,
         })
     }
 }
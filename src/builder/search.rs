@@ -96,7 +96,7 @@ impl SearchRequestBuilder {
 }
 
 /// Represents a search request to be sent to the API.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchRequest {
     /// The query to search against.
     pub query: SearchQuery,
@@ -1,49 +1,306 @@
 use crate::{
+    backends::{EmbeddingBackend, OpenAiCompatibleBackend},
     client::{
-        embeddings_client::EmbeddingClient,
-        rerank_client::DefaultRerankClient,
-        search_client::SearchClient,
-        RateLimiter,
-        voyage_client::{VoyageAiClient, VoyageAiClientConfig},
+        cache::CacheConfig,
+        circuit_breaker::{CircuitBreakerConfig, CircuitListener, CircuitTransition},
+        embeddings_client::EmbeddingsClient,
+        interceptor::Interceptor,
+        key_pool::KeySelection,
+        retry::RetryConfig,
+        splitting::SplitConfig,
+        voyage_client::VoyageAiClient,
     },
-    config::VoyageConfig,
+    config::{ConfigLoader, Settings},
     errors::VoyageError,
+    models::{embeddings::EmbeddingModel, rerank::RerankModel},
 };
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+/// Builds a [`VoyageAiClient`] from layered configuration.
+///
+/// Settings from the config file and `VOYAGE_*` environment variables apply first;
+/// anything set on the builder takes precedence over both.
 #[derive(Clone)]
 pub struct VoyageBuilder {
-    config: Option<VoyageConfig>,
+    loader: ConfigLoader,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    circuit_listeners: Vec<CircuitListener>,
+    embeddings_client: Option<Arc<dyn EmbeddingsClient>>,
+    embedding_backend: EmbeddingBackend,
+}
+
+impl Default for VoyageBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VoyageBuilder {
+    /// Creates a builder that reads the default config file and the environment.
     pub fn new() -> VoyageBuilder {
+        Self::with_loader(ConfigLoader::standard())
+    }
+
+    /// Creates a builder on top of a custom loader.
+    pub fn with_loader(loader: ConfigLoader) -> VoyageBuilder {
         VoyageBuilder {
-            config: None,
+            loader,
+            interceptors: Vec::new(),
+            circuit_listeners: Vec::new(),
+            embeddings_client: None,
+            embedding_backend: EmbeddingBackend::default(),
         }
     }
 
-    pub fn with_api_key(mut self, api_key: impl Into<String>) -> VoyageBuilder {
-        self.config = Some(VoyageConfig::new(api_key.into()));
+    pub fn with_api_key(self, api_key: impl Into<String>) -> VoyageBuilder {
+        self.with_settings(Settings {
+            api_key: Some(api_key.into().into()),
+            ..Settings::default()
+        })
+    }
+
+    /// Reads the API key from a file, such as a mounted Docker secret.
+    pub fn with_api_key_file(self, path: impl Into<PathBuf>) -> VoyageBuilder {
+        self.with_settings(Settings {
+            api_key_file: Some(path.into()),
+            ..Settings::default()
+        })
+    }
+
+    /// Runs `command` through the shell at build time and uses its output as the API key.
+    pub fn with_api_key_command(self, command: impl Into<String>) -> VoyageBuilder {
+        self.with_settings(Settings {
+            api_key_command: Some(command.into()),
+            ..Settings::default()
+        })
+    }
+
+    /// Spreads requests over several API keys, each with its own rate limits; see
+    /// [`KeyPool`](crate::client::KeyPool).
+    pub fn with_api_keys<I, K>(self, keys: I, selection: KeySelection) -> VoyageBuilder
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        self.with_settings(Settings {
+            api_keys: Some(keys.into_iter().map(|key| key.into().into()).collect()),
+            key_selection: Some(selection),
+            ..Settings::default()
+        })
+    }
+
+    /// Reads settings from the TOML file at `path` instead of the default location.
+    pub fn with_config_file(mut self, path: impl Into<PathBuf>) -> VoyageBuilder {
+        self.loader = self.loader.with_file(path);
+        self
+    }
+
+    /// Selects a profile from the config file.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> VoyageBuilder {
+        self.loader = self.loader.with_profile(profile);
+        self
+    }
+
+    pub fn with_base_url(self, base_url: impl Into<String>) -> VoyageBuilder {
+        self.with_settings(Settings {
+            base_url: Some(base_url.into()),
+            ..Settings::default()
+        })
+    }
+
+    pub fn with_embedding_model(self, model: EmbeddingModel) -> VoyageBuilder {
+        self.with_settings(Settings {
+            embedding_model: Some(model),
+            ..Settings::default()
+        })
+    }
+
+    pub fn with_rerank_model(self, model: RerankModel) -> VoyageBuilder {
+        self.with_settings(Settings {
+            rerank_model: Some(model),
+            ..Settings::default()
+        })
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> VoyageBuilder {
+        self.with_settings(Settings {
+            timeout_secs: Some(timeout.as_secs_f64()),
+            ..Settings::default()
+        })
+    }
+
+    /// Sends every request through the proxy at `url`.
+    pub fn with_proxy(self, url: impl Into<String>) -> VoyageBuilder {
+        self.with_settings(Settings {
+            proxy: Some(url.into()),
+            ..Settings::default()
+        })
+    }
+
+    /// Lets requests to `hosts` bypass the proxy.
+    pub fn with_no_proxy<I, S>(self, hosts: I) -> VoyageBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_settings(Settings {
+            no_proxy: Some(hosts.into_iter().map(Into::into).collect()),
+            ..Settings::default()
+        })
+    }
+
+    /// Ignores `HTTP_PROXY`, `HTTPS_PROXY` and the other system proxy settings.
+    pub fn without_system_proxy(self) -> VoyageBuilder {
+        self.with_settings(Settings {
+            system_proxy: Some(false),
+            ..Settings::default()
+        })
+    }
+
+    /// Trusts the root certificates in these PEM files in addition to the system store.
+    pub fn with_ca_certs<I, P>(self, paths: I) -> VoyageBuilder
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.with_settings(Settings {
+            ca_certs: Some(paths.into_iter().map(Into::into).collect()),
+            ..Settings::default()
+        })
+    }
+
+    /// Presents a client certificate for mutual TLS; `key` is a PEM (PKCS#8) private key.
+    pub fn with_client_identity(
+        self,
+        cert: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+    ) -> VoyageBuilder {
+        self.with_settings(Settings {
+            client_cert: Some(cert.into()),
+            client_key: Some(key.into()),
+            ..Settings::default()
+        })
+    }
+
+    /// Gzips request bodies of at least `threshold` bytes and accepts gzipped responses.
+    pub fn with_gzip(self, threshold: usize) -> VoyageBuilder {
+        self.with_settings(Settings {
+            gzip_threshold_bytes: Some(threshold),
+            accept_gzip: Some(true),
+            ..Settings::default()
+        })
+    }
+
+    pub fn with_retry(self, retry: RetryConfig) -> VoyageBuilder {
+        self.with_settings(Settings {
+            max_retries: Some(retry.max_retries),
+            retry_initial_delay_ms: Some(retry.initial_delay.as_millis() as u64),
+            retry_max_delay_ms: Some(retry.max_delay.as_millis() as u64),
+            ..Settings::default()
+        })
+    }
+
+    /// Enables a circuit breaker on each endpoint.
+    pub fn with_circuit_breaker(self, breaker: CircuitBreakerConfig) -> VoyageBuilder {
+        self.with_settings(Settings {
+            circuit_failure_threshold: Some(breaker.failure_threshold),
+            circuit_open_secs: Some(breaker.open_duration.as_secs_f64()),
+            circuit_half_open_probes: Some(breaker.half_open_probes),
+            ..Settings::default()
+        })
+    }
+
+    /// Embeds inputs longer than the window in overlapping windows and pools them.
+    pub fn with_split_long_inputs(self, split: SplitConfig) -> VoyageBuilder {
+        self.with_settings(Settings {
+            split_long_inputs: Some(true),
+            split_window_tokens: split.window_tokens,
+            split_overlap_tokens: Some(split.overlap_tokens),
+            split_pooling: Some(split.pooling),
+            ..Settings::default()
+        })
+    }
+
+    /// Serves repeated embeddings requests from an in-memory cache.
+    pub fn with_cache(self, cache: CacheConfig) -> VoyageBuilder {
+        self.with_settings(Settings {
+            cache_capacity: Some(cache.capacity),
+            cache_ttl_secs: cache.ttl.map(|ttl| ttl.as_secs_f64()),
+            ..Settings::default()
+        })
+    }
+
+    /// Runs `listener` whenever an endpoint's circuit changes state.
+    pub fn with_circuit_listener(
+        mut self,
+        listener: impl Fn(&CircuitTransition) + Send + Sync + 'static,
+    ) -> VoyageBuilder {
+        self.circuit_listeners.push(CircuitListener::new(listener));
+        self
+    }
+
+    /// Applies any settings; fields left unset keep their value from lower layers.
+    pub fn with_settings(mut self, settings: Settings) -> VoyageBuilder {
+        self.loader = self.loader.with_overrides(settings);
+        self
+    }
+
+    /// Registers an interceptor. Interceptors run in the order they are added.
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> VoyageBuilder {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Serves embeddings, search and chained operations from `client`; see
+    /// [`VoyageAiClient::with_embeddings_client`].
+    pub fn with_embeddings_client(
+        mut self,
+        client: impl EmbeddingsClient + 'static,
+    ) -> VoyageBuilder {
+        self.embeddings_client = Some(Arc::new(client));
+        self
+    }
+
+    /// Selects the service that embeds text. Non-Voyage backends share the client's
+    /// timeouts, retries, rate limits, interceptors and metrics, but use their own URL,
+    /// key and model. A client set with
+    /// [`with_embeddings_client`](Self::with_embeddings_client) takes precedence.
+    ///
+    /// Reranking still goes to Voyage, so [`build`](Self::build) needs a Voyage API key
+    /// whichever backend embeds; with a self-hosted backend alone, any placeholder key
+    /// will do.
+    pub fn with_embedding_backend(mut self, backend: EmbeddingBackend) -> VoyageBuilder {
+        self.embedding_backend = backend;
         self
     }
 
+    /// Builds the client, failing with `VoyageError::MissingApiKey` if no layer set a
+    /// Voyage API key, even when another backend embeds.
     pub fn build(self) -> Result<VoyageAiClient, VoyageError> {
-        let config = self.config
-            .ok_or_else(|| VoyageError::BuilderError("API key is required".to_string()))?;
-        
-        let rate_limiter = Arc::new(RateLimiter::new());
-        Ok(VoyageAiClient {
-            config: VoyageAiClientConfig {
-                config: config.clone(),
-                embeddings_client: Arc::new(EmbeddingClient::new(config.clone(), rate_limiter.clone())),
-                rerank_client: Arc::new(DefaultRerankClient::new(config.clone(), rate_limiter.clone())),
-                search_client: Arc::new(SearchClient::new(
-                    EmbeddingClient::new(config.clone(), rate_limiter.clone()),
-                    DefaultRerankClient::new(config, rate_limiter)
-                )),
+        let mut config = self.loader.load()?;
+        config.interceptors.extend(self.interceptors);
+        config.circuit_listeners.extend(self.circuit_listeners);
+        let client = VoyageAiClient::new_with_config(config)?;
+        let embeddings = match (self.embeddings_client, self.embedding_backend) {
+            (Some(embeddings), _) => Some(embeddings),
+            (None, EmbeddingBackend::Voyage) => None,
+            (None, EmbeddingBackend::OpenAiCompatible(backend)) => {
+                let backend =
+                    OpenAiCompatibleBackend::with_settings_from(backend, &client.config.config)?;
+                Some(Arc::new(backend) as Arc<dyn EmbeddingsClient>)
             }
+        };
+        Ok(match embeddings {
+            Some(embeddings) => client.with_embeddings_client(embeddings),
+            None => client,
         })
     }
-}
 
+    /// Builds a [`BlockingClient`](crate::client::BlockingClient) (`blocking` feature).
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::client::BlockingClient, VoyageError> {
+        crate::client::BlockingClient::from_client(self.build()?)
+    }
+}
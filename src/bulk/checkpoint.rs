@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bulk::input::bulk_error;
+use crate::errors::VoyageError;
+use crate::models::embeddings::EmbeddingModel;
+
+/// Progress of a bulk job as saved after every completed batch.
+///
+/// Batches are written in input order, so the completed work is always a prefix of the
+/// input: `records_done` records, whose embeddings fill the first `output_bytes` bytes of
+/// the output file. Records the API rejected count towards `records_done` and are
+/// appended to the dead-letter file instead, if the job has one.
+///
+/// A job only resumes from a checkpoint written for the same input and model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub input: PathBuf,
+    pub model: EmbeddingModel,
+    pub completed_batches: u64,
+    pub records_done: u64,
+    pub output_bytes: u64,
+    pub tokens: u64,
+    #[serde(default)]
+    pub failed_records: u64,
+    #[serde(default)]
+    pub dead_letter_bytes: u64,
+}
+
+impl Checkpoint {
+    /// A checkpoint for a job that has not completed any batch.
+    pub fn new(input: impl Into<PathBuf>, model: EmbeddingModel) -> Self {
+        Self {
+            input: input.into(),
+            model,
+            completed_batches: 0,
+            records_done: 0,
+            output_bytes: 0,
+            tokens: 0,
+            failed_records: 0,
+            dead_letter_bytes: 0,
+        }
+    }
+
+    /// Loads the checkpoint at `path`, or returns `None` if there is none.
+    pub fn load(path: &Path) -> Result<Option<Self>, VoyageError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(path).map_err(|e| bulk_error(path, e))?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| bulk_error(path, e))
+    }
+
+    /// Writes the checkpoint to `path`, replacing the previous one atomically.
+    pub fn save(&self, path: &Path) -> Result<(), VoyageError> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?).map_err(|e| bulk_error(&tmp, e))?;
+        fs::rename(&tmp, path).map_err(|e| bulk_error(path, e))
+    }
+}
@@ -0,0 +1,137 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::errors::VoyageError;
+
+/// Format of a bulk job's input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    /// One JSON object per line.
+    Jsonl,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl InputFormat {
+    /// Picks the format from the file extension: `.csv` is CSV, anything else JSONL.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => InputFormat::Csv,
+            _ => InputFormat::Jsonl,
+        }
+    }
+}
+
+/// One input to embed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputRecord {
+    /// The record's id field, or its 0-based position in the file if it has none.
+    pub id: String,
+    pub text: String,
+}
+
+/// Which fields of an input record hold the id and the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InputFields {
+    pub(crate) id: String,
+    pub(crate) text: String,
+}
+
+/// Reads the records of `path` in file order.
+pub(crate) fn read_records(
+    path: &Path,
+    format: InputFormat,
+    fields: &InputFields,
+) -> Result<Box<dyn Iterator<Item = Result<InputRecord, VoyageError>> + Send>, VoyageError> {
+    let file = File::open(path).map_err(|e| bulk_error(path, e))?;
+    let path = path.to_path_buf();
+    let fields = fields.clone();
+    match format {
+        InputFormat::Jsonl => {
+            let lines = BufReader::new(file)
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()));
+            let records = lines.enumerate().map(move |(position, (line_no, line))| {
+                let at = |e: &dyn std::fmt::Display| {
+                    bulk_error(&path, format!("line {}: {}", line_no + 1, e))
+                };
+                let value: Value =
+                    serde_json::from_str(&line.map_err(|e| at(&e))?).map_err(|e| at(&e))?;
+                jsonl_record(&value, &fields, position).map_err(|e| at(&e))
+            });
+            Ok(Box::new(records))
+        }
+        InputFormat::Csv => {
+            let mut reader = csv::Reader::from_reader(file);
+            let headers = reader.headers().map_err(|e| bulk_error(&path, e))?.clone();
+            let column = |name: &str| headers.iter().position(|h| h == name);
+            let id_column = column(&fields.id);
+            let text_column = column(&fields.text).ok_or_else(|| {
+                bulk_error(&path, format!("no '{}' column in the header", fields.text))
+            })?;
+            let records = reader
+                .into_records()
+                .enumerate()
+                .map(move |(position, row)| {
+                    let row = row.map_err(|e| bulk_error(&path, e))?;
+                    let id = match id_column.and_then(|i| row.get(i)) {
+                        Some(id) => id.to_string(),
+                        None => position.to_string(),
+                    };
+                    let text = row.get(text_column).unwrap_or_default().to_string();
+                    Ok(InputRecord { id, text })
+                });
+            Ok(Box::new(records))
+        }
+    }
+}
+
+/// Counts the records of `path` without parsing them.
+pub(crate) fn count_records(path: &Path, format: InputFormat) -> Result<u64, VoyageError> {
+    let file = File::open(path).map_err(|e| bulk_error(path, e))?;
+    match format {
+        InputFormat::Jsonl => {
+            let mut count = 0;
+            for line in BufReader::new(file).lines() {
+                if !line.map_err(|e| bulk_error(path, e))?.trim().is_empty() {
+                    count += 1;
+                }
+            }
+            Ok(count)
+        }
+        InputFormat::Csv => {
+            let mut count = 0;
+            for row in csv::Reader::from_reader(file).into_byte_records() {
+                row.map_err(|e| bulk_error(path, e))?;
+                count += 1;
+            }
+            Ok(count)
+        }
+    }
+}
+
+fn jsonl_record(
+    value: &Value,
+    fields: &InputFields,
+    position: usize,
+) -> Result<InputRecord, String> {
+    let text = match value.get(&fields.text) {
+        Some(Value::String(text)) => text.clone(),
+        Some(_) => return Err(format!("'{}' is not a string", fields.text)),
+        None => return Err(format!("missing '{}'", fields.text)),
+    };
+    let id = match value.get(&fields.id) {
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Null) | None => position.to_string(),
+        Some(other) => other.to_string(),
+    };
+    Ok(InputRecord { id, text })
+}
+
+pub(crate) fn bulk_error(path: &Path, error: impl std::fmt::Display) -> VoyageError {
+    VoyageError::BulkJobError(format!("{}: {}", path.display(), error))
+}
@@ -0,0 +1,425 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::stream::{self, StreamExt};
+use log::{debug, info};
+use serde_json::json;
+
+use crate::bulk::checkpoint::Checkpoint;
+use crate::bulk::input::{
+    bulk_error, count_records, read_records, InputFields, InputFormat, InputRecord,
+};
+use crate::client::embed_stream::MAX_BATCH_SIZE;
+use crate::client::embeddings_client::EmbeddingsClient;
+use crate::client::isolation::{create_embedding_isolated, ItemResult};
+use crate::errors::VoyageError;
+use crate::models::embeddings::{EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, InputType};
+
+/// Progress reported after every completed batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BulkProgress {
+    /// Records embedded so far, including those from earlier runs.
+    pub records_done: u64,
+    /// Records in the input, if they were counted.
+    pub records_total: Option<u64>,
+    pub batches_done: u64,
+    /// Records written to the dead-letter file so far.
+    pub failed: u64,
+    /// Tokens billed so far, including earlier runs.
+    pub tokens: u64,
+    /// Time spent in this run.
+    pub elapsed: Duration,
+    /// Records per second in this run.
+    pub throughput: f64,
+    /// Estimated time until the job finishes at the current throughput.
+    pub eta: Option<Duration>,
+}
+
+/// Summary of a finished bulk job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkReport {
+    /// Records processed, whether embedded or dead-lettered.
+    pub records: u64,
+    /// Records written to the dead-letter file.
+    pub failed: u64,
+    /// Records that were already done when this run started.
+    pub resumed_from: u64,
+    pub batches: u64,
+    pub tokens: u64,
+    /// Time spent in this run.
+    pub elapsed: Duration,
+}
+
+type ProgressCallback = Arc<dyn Fn(&BulkProgress) + Send + Sync>;
+
+/// Embeds every record of a JSONL or CSV file into a JSONL output file.
+///
+/// Each output line is `{"id": ..., "embedding": [...]}`, in input order. After every
+/// batch the output is synced and a checkpoint is written next to it; running the same
+/// job again resumes after the last checkpointed batch. Delete the checkpoint and the
+/// output to start over.
+///
+/// By default a batch the API rejects stops the job. With [`BulkJob::with_dead_letter`]
+/// the rejected inputs are isolated and set aside instead.
+#[derive(Clone)]
+pub struct BulkJob {
+    client: Arc<dyn EmbeddingsClient>,
+    input: PathBuf,
+    output: PathBuf,
+    checkpoint: PathBuf,
+    dead_letter: Option<PathBuf>,
+    format: InputFormat,
+    fields: InputFields,
+    batch_size: usize,
+    concurrency: usize,
+    model: Option<EmbeddingModel>,
+    input_type: Option<InputType>,
+    count_total: bool,
+    progress: Option<ProgressCallback>,
+}
+
+impl fmt::Debug for BulkJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkJob")
+            .field("input", &self.input)
+            .field("output", &self.output)
+            .field("checkpoint", &self.checkpoint)
+            .field("dead_letter", &self.dead_letter)
+            .field("format", &self.format)
+            .field("batch_size", &self.batch_size)
+            .field("concurrency", &self.concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BulkJob {
+    /// Creates a job embedding with `client`, which may be an
+    /// [`EmbeddingClient`](crate::client::embeddings_client::EmbeddingClient), a backend or
+    /// a shared `Arc<dyn EmbeddingsClient>`, reading `input` and writing `output`. The
+    /// format is taken from the input's extension and the checkpoint is
+    /// `<output>.checkpoint.json`.
+    pub fn new(
+        client: impl EmbeddingsClient + 'static,
+        input: impl Into<PathBuf>,
+        output: impl Into<PathBuf>,
+    ) -> Self {
+        let input = input.into();
+        let output = output.into();
+        let mut checkpoint = output.as_os_str().to_owned();
+        checkpoint.push(".checkpoint.json");
+        Self {
+            client: Arc::new(client),
+            format: InputFormat::from_path(&input),
+            input,
+            output,
+            checkpoint: PathBuf::from(checkpoint),
+            dead_letter: None,
+            fields: InputFields {
+                id: "id".to_string(),
+                text: "text".to_string(),
+            },
+            batch_size: MAX_BATCH_SIZE,
+            concurrency: 4,
+            model: None,
+            input_type: None,
+            count_total: true,
+            progress: None,
+        }
+    }
+
+    pub fn with_format(mut self, format: InputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint = path.into();
+        self
+    }
+
+    /// Bisects batches rejected with a 400 to find the offending inputs (see
+    /// [`create_embedding_isolated`]) and appends each of them to `path`
+    /// as `{"id": ..., "text": ..., "error": ...}` instead of failing the job.
+    pub fn with_dead_letter(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dead_letter = Some(path.into());
+        self
+    }
+
+    /// Reads ids from `field` instead of `id`. Records without one are numbered.
+    pub fn with_id_field(mut self, field: impl Into<String>) -> Self {
+        self.fields.id = field.into();
+        self
+    }
+
+    /// Reads texts from `field` instead of `text`.
+    pub fn with_text_field(mut self, field: impl Into<String>) -> Self {
+        self.fields.text = field.into();
+        self
+    }
+
+    /// Records per request, at most 128.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.clamp(1, MAX_BATCH_SIZE);
+        self
+    }
+
+    /// Requests in flight at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_model(mut self, model: EmbeddingModel) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn with_input_type(mut self, input_type: InputType) -> Self {
+        self.input_type = Some(input_type);
+        self
+    }
+
+    /// Skips counting the input's records; progress then has no total or ETA.
+    ///
+    /// By default the records are counted in the background while the job runs, and
+    /// progress has a total and ETA once the count is done.
+    pub fn without_total(mut self) -> Self {
+        self.count_total = false;
+        self
+    }
+
+    /// Calls `callback` after every completed batch.
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(&BulkProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Runs the job to completion, resuming from the checkpoint if there is one.
+    ///
+    /// Fails without embedding anything if the checkpoint was written for another input
+    /// file or model. A failed batch stops the job with its error; everything before it
+    /// stays checkpointed.
+    pub async fn run(&self) -> Result<BulkReport, VoyageError> {
+        let started = Instant::now();
+        let model = self.model();
+        let mut checkpoint = match Checkpoint::load(&self.checkpoint)? {
+            Some(checkpoint) if !same_file(&checkpoint.input, &self.input) => {
+                return Err(bulk_error(
+                    &self.checkpoint,
+                    format!("checkpoint belongs to {}", checkpoint.input.display()),
+                ));
+            }
+            Some(checkpoint) if checkpoint.model != model => {
+                return Err(bulk_error(
+                    &self.checkpoint,
+                    format!(
+                        "checkpoint was written with {}, not {}",
+                        checkpoint.model.as_str(),
+                        model.as_str()
+                    ),
+                ));
+            }
+            Some(checkpoint) => checkpoint,
+            None => Checkpoint::new(&self.input, model),
+        };
+        let resumed_from = checkpoint.records_done;
+        if resumed_from > 0 {
+            info!(
+                "Resuming bulk job for {} after {} records",
+                self.input.display(),
+                resumed_from
+            );
+        }
+
+        let mut output = open_at(&self.output, checkpoint.output_bytes)?;
+        let mut dead_letter = match &self.dead_letter {
+            Some(path) => Some(open_at(path, checkpoint.dead_letter_bytes)?),
+            None => None,
+        };
+
+        let mut counting = self.count_total.then(|| {
+            let (path, format) = (self.input.clone(), self.format);
+            tokio::task::spawn_blocking(move || count_records(&path, format))
+        });
+        let mut total = None;
+
+        let records =
+            read_records(&self.input, self.format, &self.fields)?.skip(resumed_from as usize);
+        let batches = stream::iter(records)
+            .chunks(self.batch_size)
+            .map(|chunk| self.embed_batch(chunk))
+            .buffered(self.concurrency);
+        let mut batches = std::pin::pin!(batches);
+
+        while let Some(batch) = batches.next().await {
+            let (records, results, tokens) = batch?;
+            let mut lines = Vec::new();
+            let mut rejected = Vec::new();
+            let mut failed = 0;
+            for (record, result) in records.iter().zip(results) {
+                let (line, buffer) = match result {
+                    Ok(embedding) => (
+                        json!({ "id": record.id, "embedding": embedding }),
+                        &mut lines,
+                    ),
+                    Err(error) => {
+                        failed += 1;
+                        let line = json!({
+                            "id": record.id,
+                            "text": record.text,
+                            "error": error.to_string(),
+                        });
+                        (line, &mut rejected)
+                    }
+                };
+                serde_json::to_writer(&mut *buffer, &line)?;
+                buffer.push(b'\n');
+            }
+            write_synced(&mut output, &lines, &self.output)?;
+            if let (Some(file), Some(path)) = (dead_letter.as_mut(), &self.dead_letter) {
+                write_synced(file, &rejected, path)?;
+            }
+
+            checkpoint.completed_batches += 1;
+            checkpoint.records_done += records.len() as u64;
+            checkpoint.failed_records += failed;
+            checkpoint.output_bytes += lines.len() as u64;
+            checkpoint.dead_letter_bytes += rejected.len() as u64;
+            checkpoint.tokens += tokens;
+            checkpoint.save(&self.checkpoint)?;
+            debug!(
+                "Checkpointed batch {} ({} records)",
+                checkpoint.completed_batches, checkpoint.records_done
+            );
+
+            if counting.as_ref().is_some_and(|task| task.is_finished()) {
+                if let Some(task) = counting.take() {
+                    total = match task.await {
+                        Ok(Ok(count)) => Some(count),
+                        Ok(Err(e)) => {
+                            debug!("Cannot count input records: {}", e);
+                            None
+                        }
+                        Err(e) => {
+                            debug!("Counting input records failed: {}", e);
+                            None
+                        }
+                    };
+                }
+            }
+            if let Some(callback) = &self.progress {
+                callback(&progress(
+                    &checkpoint,
+                    resumed_from,
+                    total,
+                    started.elapsed(),
+                ));
+            }
+        }
+
+        Ok(BulkReport {
+            records: checkpoint.records_done,
+            failed: checkpoint.failed_records,
+            resumed_from,
+            batches: checkpoint.completed_batches,
+            tokens: checkpoint.tokens,
+            elapsed: started.elapsed(),
+        })
+    }
+
+    fn model(&self) -> EmbeddingModel {
+        self.model.unwrap_or(self.client.embedding_model())
+    }
+
+    async fn embed_batch(
+        &self,
+        chunk: Vec<Result<InputRecord, VoyageError>>,
+    ) -> Result<(Vec<InputRecord>, Vec<ItemResult>, u64), VoyageError> {
+        let records = chunk.into_iter().collect::<Result<Vec<_>, _>>()?;
+        let texts = records.iter().map(|r| r.text.clone()).collect();
+        let request = EmbeddingsRequest {
+            input: EmbeddingsInput::Multiple(texts),
+            model: self.model(),
+            input_type: self.input_type,
+            truncation: None,
+            encoding_format: None,
+        };
+        if self.dead_letter.is_some() {
+            let batch = create_embedding_isolated(&*self.client, &request).await?;
+            return Ok((records, batch.items, batch.total_tokens));
+        }
+
+        let mut response = self.client.create_embedding(&request).await?;
+        if response.data.len() != records.len() {
+            return Err(VoyageError::JsonError(format!(
+                "Expected {} embeddings, got {}",
+                records.len(),
+                response.data.len()
+            )));
+        }
+        response.data.sort_by_key(|d| d.index);
+        let embeddings = response.data.into_iter().map(|d| Ok(d.embedding)).collect();
+        Ok((records, embeddings, response.usage.total_tokens as u64))
+    }
+}
+
+/// Returns `true` if `a` and `b` name the same file, e.g. as relative and absolute paths.
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b
+        || matches!(
+            (fs::canonicalize(a), fs::canonicalize(b)),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// Opens `path` for appending after its first `len` bytes. Anything past them was written
+/// by a batch that never completed.
+fn open_at(path: &Path, len: u64) -> Result<File, VoyageError> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .map_err(|e| bulk_error(path, e))?;
+    file.set_len(len)
+        .and_then(|_| file.seek(SeekFrom::End(0)))
+        .map_err(|e| bulk_error(path, e))?;
+    Ok(file)
+}
+
+fn write_synced(file: &mut File, bytes: &[u8], path: &Path) -> Result<(), VoyageError> {
+    file.write_all(bytes)
+        .and_then(|_| file.sync_data())
+        .map_err(|e| bulk_error(path, e))
+}
+
+fn progress(
+    checkpoint: &Checkpoint,
+    resumed_from: u64,
+    total: Option<u64>,
+    elapsed: Duration,
+) -> BulkProgress {
+    let done_this_run = checkpoint.records_done - resumed_from;
+    let throughput = done_this_run as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    let eta = total.filter(|_| throughput > 0.0).map(|total| {
+        let remaining = total.saturating_sub(checkpoint.records_done);
+        Duration::from_secs_f64(remaining as f64 / throughput)
+    });
+    BulkProgress {
+        records_done: checkpoint.records_done,
+        records_total: total,
+        batches_done: checkpoint.completed_batches,
+        failed: checkpoint.failed_records,
+        tokens: checkpoint.tokens,
+        elapsed,
+        throughput,
+        eta,
+    }
+}
@@ -0,0 +1,27 @@
+//! Resumable bulk embedding jobs.
+//!
+//! A [`BulkJob`] embeds every record of a JSONL or CSV file, writing the results to a
+//! JSONL file and a [`Checkpoint`] after each batch so an interrupted job picks up where
+//! it stopped.
+//!
+//! ```no_run
+//! # async fn run(client: voyageai::client::embeddings_client::EmbeddingClient)
+//! # -> Result<(), voyageai::VoyageError> {
+//! use voyageai::bulk::BulkJob;
+//!
+//! let report = BulkJob::new(client, "corpus.jsonl", "embeddings.jsonl")
+//!     .with_progress(|p| eprintln!("{} records, eta {:?}", p.records_done, p.eta))
+//!     .run()
+//!     .await?;
+//! println!("{} records, {} tokens", report.records, report.tokens);
+//! # Ok(())
+//! # }
+//! ```
+
+mod checkpoint;
+mod input;
+mod job;
+
+pub use checkpoint::Checkpoint;
+pub use input::{InputFormat, InputRecord};
+pub use job::{BulkJob, BulkProgress, BulkReport};
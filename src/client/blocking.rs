@@ -0,0 +1,134 @@
+//! A synchronous wrapper around [`VoyageAiClient`] (`blocking` feature).
+//!
+//! [`BlockingClient`] owns a small Tokio runtime and blocks the calling thread on it. It
+//! can be called from plain threads and from inside another Tokio runtime: there the
+//! call runs on a helper thread, so it never panics with "Cannot start a runtime from
+//! within a runtime", although it still blocks the calling task's worker thread.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::thread;
+
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::builder::search::SearchRequest;
+use crate::client::voyage_client::VoyageAiClient;
+use crate::client::SearchResult;
+use crate::config::VoyageConfig;
+use crate::errors::VoyageError;
+use crate::models::embeddings::{EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse};
+use crate::models::rerank::{RerankRequest, RerankResponse};
+
+/// Blocking counterpart of [`VoyageAiClient`]. Clones share the client and the runtime.
+#[derive(Clone)]
+pub struct BlockingClient {
+    client: VoyageAiClient,
+    runtime: Arc<OwnedRuntime>,
+}
+
+impl BlockingClient {
+    /// Wraps `client`, starting the runtime its calls run on.
+    pub fn from_client(client: VoyageAiClient) -> Result<Self, VoyageError> {
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("voyageai-blocking")
+            .enable_all()
+            .build()
+            .map_err(|e| VoyageError::RuntimeError(e.to_string()))?;
+        Ok(Self {
+            client,
+            runtime: Arc::new(OwnedRuntime(Some(runtime))),
+        })
+    }
+
+    /// Creates a client from `config`; see [`VoyageAiClient::new_with_config`].
+    pub fn new_with_config(config: VoyageConfig) -> Result<Self, VoyageError> {
+        Self::from_client(VoyageAiClient::new_with_config(config)?)
+    }
+
+    /// Creates a client from the config file and environment; see [`VoyageAiClient::new`].
+    pub fn new() -> Result<Self, VoyageError> {
+        Self::from_client(VoyageAiClient::new()?)
+    }
+
+    pub fn with_key(api_key: impl Into<String>) -> Result<Self, VoyageError> {
+        Self::from_client(VoyageAiClient::with_key(api_key)?)
+    }
+
+    /// The wrapped async client.
+    pub fn async_client(&self) -> &VoyageAiClient {
+        &self.client
+    }
+
+    pub fn embed(
+        &self,
+        input: impl Into<EmbeddingsInput>,
+    ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error>> {
+        let request = EmbeddingsRequest {
+            input: input.into(),
+            model: self.client.config.config.embedding_model,
+            input_type: None,
+            truncation: None,
+            encoding_format: None,
+        };
+        // `VoyageAiClient::embed` boxes its error, which cannot cross threads, so call
+        // `embeddings` and box afterwards.
+        self.block_on(|| self.client.embeddings(request))
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+    }
+
+    pub fn embed_text(&self, text: impl Into<String>) -> Result<Vec<f32>, VoyageError> {
+        let text = text.into();
+        self.block_on(|| self.client.embed_text(text))
+    }
+
+    pub fn embed_batch(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, VoyageError> {
+        self.block_on(|| self.client.embed_batch(texts))
+    }
+
+    pub fn rerank(&self, request: RerankRequest) -> Result<RerankResponse, VoyageError> {
+        self.block_on(|| self.client.rerank(request))
+    }
+
+    pub fn search(&self, request: SearchRequest) -> Result<Vec<SearchResult>, VoyageError> {
+        self.block_on(|| self.client.search(request))
+    }
+
+    /// Runs the future made by `make` to completion. Inside a Tokio runtime the future is
+    /// made and driven on a scoped helper thread, where blocking is allowed.
+    fn block_on<F, Fut>(&self, make: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: Future,
+        Fut::Output: Send,
+    {
+        let runtime = self.runtime.get();
+        if Handle::try_current().is_err() {
+            return runtime.block_on(make());
+        }
+        thread::scope(|scope| {
+            scope
+                .spawn(|| runtime.block_on(make()))
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    }
+}
+
+/// A runtime that shuts down without blocking, so the last clone of a
+/// [`BlockingClient`] can be dropped inside async code.
+struct OwnedRuntime(Option<Runtime>);
+
+impl OwnedRuntime {
+    fn get(&self) -> &Runtime {
+        self.0.as_ref().expect("runtime is only taken on drop")
+    }
+}
+
+impl Drop for OwnedRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
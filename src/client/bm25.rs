@@ -0,0 +1,119 @@
+//! A local BM25 [`Reranker`], for offline use or as a cheap first stage before the API.
+
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+
+use crate::errors::VoyageError;
+use crate::models::rerank::RerankResult;
+use crate::traits::llm::{finish_rerank, RerankOptions, Reranker};
+
+/// Term-frequency saturation unless configured otherwise.
+pub const DEFAULT_K1: f64 = 1.5;
+
+/// Document-length normalization unless configured otherwise.
+pub const DEFAULT_B: f64 = 0.75;
+
+/// Scores documents with Okapi BM25, using term statistics of the documents passed to
+/// each call. Text is split into lowercased alphanumeric words. Scores are not bounded
+/// to 0.0..=1.0 like the API's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bm25Reranker {
+    pub k1: f64,
+    pub b: f64,
+}
+
+impl Default for Bm25Reranker {
+    fn default() -> Self {
+        Self {
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
+        }
+    }
+}
+
+impl Bm25Reranker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_k1(mut self, k1: f64) -> Self {
+        self.k1 = k1;
+        self
+    }
+
+    pub fn with_b(mut self, b: f64) -> Self {
+        self.b = b;
+        self
+    }
+
+    /// BM25 score of every document for `query`, in input order.
+    pub fn scores(&self, query: &str, documents: &[String]) -> Vec<f64> {
+        let documents: Vec<Vec<String>> = documents.iter().map(|d| words(d)).collect();
+        if documents.is_empty() {
+            return Vec::new();
+        }
+
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for terms in &documents {
+            let unique: HashSet<&str> = terms.iter().map(String::as_str).collect();
+            for term in unique {
+                *document_frequency.entry(term).or_default() += 1;
+            }
+        }
+        let count = documents.len() as f64;
+        let average_length = documents.iter().map(Vec::len).sum::<usize>() as f64 / count;
+
+        let query_terms: HashSet<String> = words(query).into_iter().collect();
+        documents
+            .iter()
+            .map(|terms| {
+                let length = terms.len() as f64;
+                let norm = if average_length > 0.0 {
+                    1.0 - self.b + self.b * length / average_length
+                } else {
+                    1.0
+                };
+                query_terms
+                    .iter()
+                    .filter_map(|term| {
+                        let df = *document_frequency.get(term.as_str())? as f64;
+                        let idf = ((count - df + 0.5) / (df + 0.5) + 1.0).ln();
+                        let tf = terms.iter().filter(|t| *t == term).count() as f64;
+                        Some(idf * tf * (self.k1 + 1.0) / (tf + self.k1 * norm))
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Reranker for Bm25Reranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>, VoyageError> {
+        let results = self
+            .scores(query, documents)
+            .into_iter()
+            .enumerate()
+            .map(|(index, relevance_score)| RerankResult {
+                relevance_score,
+                index,
+                document: None,
+            })
+            .collect();
+        Ok(finish_rerank(results, documents, options))
+    }
+}
+
+/// Lowercased alphanumeric words of `text`.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,246 @@
+//! In-memory cache of embedding responses.
+//!
+//! [`CachedEmbeddingsClient`] puts an [`EmbeddingCache`] in front of any
+//! [`EmbeddingsClient`], whether the Voyage HTTP client or another backend. Responses are
+//! keyed by the request's full JSON form rather than a hash of it, so a request is served
+//! from the cache only if its texts, model, input type and every other field match. The
+//! cache is shared by clones of the wrapper.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use log::debug;
+
+use crate::client::embeddings_client::EmbeddingsClient;
+use crate::errors::VoyageError;
+use crate::models::embeddings::{EmbeddingModel, EmbeddingsRequest, EmbeddingsResponse};
+
+/// Responses kept unless configured otherwise.
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Size and lifetime of the embeddings cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Most responses kept; the least recently used is evicted beyond this.
+    pub capacity: usize,
+    /// How long a response may be served from the cache; forever when `None`.
+    pub ttl: Option<Duration>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CACHE_CAPACITY,
+            ttl: None,
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttl: None,
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Hit and miss counts of an [`EmbeddingCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Responses currently cached.
+    pub entries: usize,
+}
+
+/// A bounded, least-recently-used cache of embedding responses.
+#[derive(Debug, Clone)]
+pub struct EmbeddingCache {
+    config: CacheConfig,
+    state: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Keyed by the request serialized to JSON.
+    entries: HashMap<String, Entry>,
+    /// Incremented on every access; entries remember when they were last used.
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: EmbeddingsResponse,
+    inserted: Instant,
+    last_used: u64,
+}
+
+impl EmbeddingCache {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(CacheState::default())),
+        }
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Returns the cached response for `request`, if there is a fresh one.
+    pub fn get(&self, request: &EmbeddingsRequest) -> Option<EmbeddingsResponse> {
+        let key = serde_json::to_string(request).ok()?;
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        state.clock += 1;
+        let clock = state.clock;
+        let expired = match state.entries.get_mut(&key) {
+            Some(entry) if self.is_fresh(entry) => {
+                entry.last_used = clock;
+                let response = entry.response.clone();
+                state.hits += 1;
+                return Some(response);
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            state.entries.remove(&key);
+        }
+        state.misses += 1;
+        None
+    }
+
+    /// Caches `response` for `request`. Requests that cannot be serialized are not cached.
+    pub fn insert(&self, request: &EmbeddingsRequest, response: &EmbeddingsResponse) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let Ok(key) = serde_json::to_string(request) else {
+            return;
+        };
+        let mut state = self.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.config.capacity {
+            // A linear scan keeps the cache simple; it only runs when the cache is full.
+            if let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            {
+                debug!("Evicting least recently used embeddings cache entry");
+                state.entries.remove(&oldest);
+            }
+        }
+        state.entries.insert(
+            key,
+            Entry {
+                response: response.clone(),
+                inserted: Instant::now(),
+                last_used: clock,
+            },
+        );
+    }
+
+    /// Drops every cached response. Hit and miss counts are kept.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            entries: state.entries.len(),
+        }
+    }
+
+    fn is_fresh(&self, entry: &Entry) -> bool {
+        self.config
+            .ttl
+            .is_none_or(|ttl| entry.inserted.elapsed() < ttl)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        // The state stays consistent even if a holder panicked, so poisoning is ignored.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An [`EmbeddingsClient`] that answers requests identical to an earlier successful one
+/// from an [`EmbeddingCache`] and passes the rest on to `inner`.
+#[derive(Debug, Clone)]
+pub struct CachedEmbeddingsClient<C> {
+    inner: C,
+    cache: EmbeddingCache,
+}
+
+impl<C: EmbeddingsClient> CachedEmbeddingsClient<C> {
+    /// Wraps `inner` with a new, empty cache.
+    pub fn new(inner: C, config: CacheConfig) -> Self {
+        Self::with_cache(inner, EmbeddingCache::new(config))
+    }
+
+    /// Wraps `inner` with an existing cache, which must only ever hold responses from
+    /// the same service and model.
+    pub fn with_cache(inner: C, cache: EmbeddingCache) -> Self {
+        Self { inner, cache }
+    }
+
+    pub fn cache(&self) -> &EmbeddingCache {
+        &self.cache
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<C: EmbeddingsClient> EmbeddingsClient for CachedEmbeddingsClient<C> {
+    fn embedding_model(&self) -> EmbeddingModel {
+        self.inner.embedding_model()
+    }
+
+    fn embedding_model_name(&self) -> &str {
+        self.inner.embedding_model_name()
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.inner.embedding_dimension()
+    }
+
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        if let Some(response) = self.cache.get(request) {
+            debug!("Serving embeddings request from cache");
+            return Ok(response);
+        }
+        let response = self.inner.create_embedding(request).await?;
+        self.cache.insert(request, &response);
+        Ok(response)
+    }
+}
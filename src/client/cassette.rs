@@ -0,0 +1,231 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use log::{debug, info};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::client::transport::RawResponse;
+use crate::errors::VoyageError;
+
+/// Placeholder written to cassettes wherever the API key appeared.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// How a [`Cassette`] treats API calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CassetteMode {
+    /// Calls the API and saves every interaction, replacing earlier recordings
+    /// of the same request.
+    Record,
+    /// Never touches the network; a request without a recording fails with
+    /// `VoyageError::CassetteMiss`.
+    ReplayOnly,
+    /// Calls the API and records nothing.
+    Passthrough,
+}
+
+/// The parts of a request a recording is matched on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CassetteRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Value,
+}
+
+/// A response as stored in a cassette.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CassetteResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// One request/response pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: CassetteRequest,
+    pub response: CassetteResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// Record/replay store for API interactions, backed by a JSON file.
+///
+/// Requests match on method, endpoint path and body. Bodies are compared as parsed
+/// JSON, so key order and whitespace do not matter. Clones share the same recordings.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+impl Cassette {
+    /// Opens the cassette at `path`, loading any recordings it already holds.
+    ///
+    /// A missing file is treated as an empty cassette, except in `ReplayOnly` mode.
+    pub fn open(path: impl Into<PathBuf>, mode: CassetteMode) -> Result<Self, VoyageError> {
+        let path = path.into();
+        let interactions = if path.exists() {
+            let contents = fs::read_to_string(&path)?;
+            serde_json::from_str::<CassetteFile>(&contents)?.interactions
+        } else if mode == CassetteMode::ReplayOnly {
+            return Err(VoyageError::CassetteMiss(format!(
+                "cassette {} does not exist",
+                path.display()
+            )));
+        } else {
+            Vec::new()
+        };
+        debug!(
+            "Opened cassette {} in {:?} mode with {} interactions",
+            path.display(),
+            mode,
+            interactions.len()
+        );
+
+        Ok(Self {
+            path,
+            mode,
+            interactions: Arc::new(Mutex::new(interactions)),
+        })
+    }
+
+    /// Returns the cassette's mode.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Returns the cassette's file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a copy of every recorded interaction.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().clone()
+    }
+
+    /// Builds the matchable form of a request, with `api_key` scrubbed from the body.
+    pub fn request_key(method: &str, path: &str, body: &Value, api_key: &str) -> CassetteRequest {
+        CassetteRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: redact_value(body.clone(), api_key),
+        }
+    }
+
+    /// Returns the recorded response for `request`, if there is one.
+    pub fn find(&self, request: &CassetteRequest) -> Option<RawResponse> {
+        self.lock()
+            .iter()
+            .find(|interaction| interaction.request == *request)
+            .map(|interaction| to_raw_response(&interaction.response))
+    }
+
+    /// Replays `request`, failing with `VoyageError::CassetteMiss` if it was never recorded.
+    pub fn replay(&self, request: &CassetteRequest) -> Result<RawResponse, VoyageError> {
+        self.find(request).ok_or_else(|| {
+            VoyageError::CassetteMiss(format!(
+                "no recording for {} {} in {}",
+                request.method,
+                request.path,
+                self.path.display()
+            ))
+        })
+    }
+
+    /// Stores `response` for `request` and saves the cassette to disk.
+    pub fn record(
+        &self,
+        request: CassetteRequest,
+        response: &RawResponse,
+        api_key: &str,
+    ) -> Result<(), VoyageError> {
+        let response = CassetteResponse {
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    let value = value.to_str().ok()?;
+                    Some((name.to_string(), redact(value, api_key)))
+                })
+                .collect(),
+            body: redact(&response.body, api_key),
+        };
+
+        let mut interactions = self.lock();
+        interactions.retain(|interaction| interaction.request != request);
+        interactions.push(Interaction { request, response });
+
+        let contents = serde_json::to_string_pretty(&CassetteFile {
+            interactions: interactions.clone(),
+        })?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, contents)?;
+        info!(
+            "Recorded interaction to cassette {} ({} total)",
+            self.path.display(),
+            interactions.len()
+        );
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Interaction>> {
+        self.interactions.lock().expect("cassette poisoned")
+    }
+}
+
+fn to_raw_response(response: &CassetteResponse) -> RawResponse {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &response.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    RawResponse {
+        status: StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        headers,
+        body: response.body.clone(),
+    }
+}
+
+fn redact(text: &str, api_key: &str) -> String {
+    if api_key.is_empty() {
+        text.to_string()
+    } else {
+        text.replace(api_key, REDACTED)
+    }
+}
+
+fn redact_value(value: Value, api_key: &str) -> Value {
+    match value {
+        Value::String(text) => Value::String(redact(&text, api_key)),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| redact_value(item, api_key))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, item)| (key, redact_value(item, api_key)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+
+use crate::client::client_limiter::Endpoint;
+use crate::errors::VoyageError;
+
+/// Thresholds for the per-endpoint circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed calls that open the circuit.
+    pub failure_threshold: u32,
+    /// How long an open circuit fails calls fast before letting probes through.
+    pub open_duration: Duration,
+    /// Probe calls allowed while half-open; this many must succeed to close the circuit.
+    pub half_open_probes: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 1,
+        }
+    }
+}
+
+/// State of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through; failures are counted.
+    Closed,
+    /// Calls fail fast with `VoyageError::CircuitOpen`.
+    Open,
+    /// A limited number of probe calls go through to test whether the endpoint recovered.
+    HalfOpen,
+}
+
+/// A change of state, passed to listeners registered with
+/// [`VoyageConfig::with_circuit_listener`](crate::config::VoyageConfig::with_circuit_listener).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitTransition {
+    pub endpoint: Endpoint,
+    pub from: CircuitState,
+    pub to: CircuitState,
+}
+
+/// Callback run on every state transition.
+#[derive(Clone)]
+pub struct CircuitListener(Arc<dyn Fn(&CircuitTransition) + Send + Sync>);
+
+impl CircuitListener {
+    pub fn new(callback: impl Fn(&CircuitTransition) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+}
+
+impl fmt::Debug for CircuitListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CircuitListener")
+    }
+}
+
+/// Circuit breaker for one endpoint.
+///
+/// A call counts as failed when it ends, after any retries, in a 5xx response, a timeout
+/// or a connection error. Other errors mean the endpoint is up and count as successes.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    endpoint: Endpoint,
+    config: CircuitBreakerConfig,
+    listeners: Vec<CircuitListener>,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+    probes_in_flight: u32,
+    probe_successes: u32,
+}
+
+/// Permission to make one call, obtained from [`CircuitBreaker::acquire`].
+///
+/// Dropping it without calling [`CircuitPermit::record`] frees its probe slot without
+/// counting an outcome, so cancelled calls do not wedge a half-open circuit.
+#[derive(Debug)]
+pub struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl CircuitPermit<'_> {
+    /// Records whether the call succeeded.
+    pub fn record(mut self, success: bool) {
+        self.recorded = true;
+        self.breaker.record(self.probe, success);
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.recorded {
+            self.breaker.lock().probes_in_flight -= 1;
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(
+        endpoint: Endpoint,
+        config: CircuitBreakerConfig,
+        listeners: Vec<CircuitListener>,
+    ) -> Self {
+        Self {
+            endpoint,
+            config,
+            listeners,
+            state: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: Instant::now(),
+                probes_in_flight: 0,
+                probe_successes: 0,
+            }),
+        }
+    }
+
+    /// Returns the current state. An open circuit whose open duration has passed reports
+    /// `Open` until the next call turns it half-open.
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Lets a call through, or fails with `VoyageError::CircuitOpen`.
+    pub fn acquire(&self) -> Result<CircuitPermit<'_>, VoyageError> {
+        let mut inner = self.lock();
+        let mut transition = None;
+        if inner.state == CircuitState::Open {
+            let elapsed = inner.opened_at.elapsed();
+            if elapsed < self.config.open_duration {
+                return Err(self.open_error(self.config.open_duration - elapsed));
+            }
+            transition = Some(self.set_state(&mut inner, CircuitState::HalfOpen));
+        }
+
+        let probe = inner.state == CircuitState::HalfOpen;
+        if probe {
+            if inner.probes_in_flight >= self.config.half_open_probes.max(1) {
+                drop(inner);
+                self.notify(transition);
+                return Err(self.open_error(Duration::ZERO));
+            }
+            inner.probes_in_flight += 1;
+        }
+        drop(inner);
+        self.notify(transition);
+        Ok(CircuitPermit {
+            breaker: self,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn record(&self, probe: bool, success: bool) {
+        let mut inner = self.lock();
+        if probe {
+            inner.probes_in_flight -= 1;
+        }
+        let transition = match (inner.state, success) {
+            (CircuitState::Closed, true) => {
+                inner.consecutive_failures = 0;
+                None
+            }
+            (CircuitState::Closed, false) => {
+                inner.consecutive_failures += 1;
+                (inner.consecutive_failures >= self.config.failure_threshold.max(1))
+                    .then(|| self.set_state(&mut inner, CircuitState::Open))
+            }
+            (CircuitState::HalfOpen, true) if probe => {
+                inner.probe_successes += 1;
+                (inner.probe_successes >= self.config.half_open_probes.max(1))
+                    .then(|| self.set_state(&mut inner, CircuitState::Closed))
+            }
+            (CircuitState::HalfOpen, false) if probe => {
+                Some(self.set_state(&mut inner, CircuitState::Open))
+            }
+            // Outcomes of calls that started before the last transition.
+            _ => None,
+        };
+        drop(inner);
+        self.notify(transition);
+    }
+
+    fn set_state(&self, inner: &mut BreakerState, to: CircuitState) -> CircuitTransition {
+        let from = inner.state;
+        inner.state = to;
+        inner.consecutive_failures = 0;
+        inner.probe_successes = 0;
+        if to == CircuitState::Open {
+            inner.opened_at = Instant::now();
+        }
+        match to {
+            CircuitState::Open => warn!(
+                "Circuit for {} opened; failing calls fast for {:?}",
+                self.endpoint.name(),
+                self.config.open_duration
+            ),
+            CircuitState::HalfOpen => info!("Circuit for {} half-open", self.endpoint.name()),
+            CircuitState::Closed => info!("Circuit for {} closed", self.endpoint.name()),
+        }
+        CircuitTransition {
+            endpoint: self.endpoint,
+            from,
+            to,
+        }
+    }
+
+    fn notify(&self, transition: Option<CircuitTransition>) {
+        if let Some(transition) = transition {
+            for listener in &self.listeners {
+                (listener.0)(&transition);
+            }
+        }
+    }
+
+    fn open_error(&self, retry_in: Duration) -> VoyageError {
+        VoyageError::CircuitOpen {
+            endpoint: self.endpoint.name().to_string(),
+            retry_in,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BreakerState> {
+        self.state.lock().expect("circuit breaker state poisoned")
+    }
+}
+
+/// One circuit breaker per endpoint.
+#[derive(Debug)]
+pub(crate) struct EndpointCircuits {
+    embeddings: CircuitBreaker,
+    rerank: CircuitBreaker,
+}
+
+impl EndpointCircuits {
+    pub(crate) fn new(config: CircuitBreakerConfig, listeners: &[CircuitListener]) -> Self {
+        Self {
+            embeddings: CircuitBreaker::new(Endpoint::Embeddings, config, listeners.to_vec()),
+            rerank: CircuitBreaker::new(Endpoint::Rerank, config, listeners.to_vec()),
+        }
+    }
+
+    pub(crate) fn get(&self, endpoint: Endpoint) -> &CircuitBreaker {
+        match endpoint {
+            Endpoint::Embeddings => &self.embeddings,
+            Endpoint::Rerank => &self.rerank,
+        }
+    }
+}
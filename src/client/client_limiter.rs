@@ -11,7 +11,49 @@ pub struct RateLimiter {
     reranking_limiter: Arc<Mutex<ApiLimiter>>,
 }
 
-impl RateLimiter {
+/// An API endpoint with its own rate limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Embeddings,
+    Rerank,
+}
+
+impl Endpoint {
+    /// Returns the endpoint's path relative to the base URL.
+    pub const fn path(&self) -> &'static str {
+        match self {
+            Endpoint::Embeddings => "/embeddings",
+            Endpoint::Rerank => "/rerank",
+        }
+    }
+
+    /// Returns a short name for logs and spans.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Endpoint::Embeddings => "embeddings",
+            Endpoint::Rerank => "rerank",
+        }
+    }
+}
+
+/// Requests- and tokens-per-minute limits enforced client-side for each endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+    pub embeddings_rpm: u32,
+    pub embeddings_tpm: u32,
+    pub rerank_rpm: u32,
+    pub rerank_tpm: u32,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            embeddings_rpm: 300,
+            embeddings_tpm: 1_000_000,
+            rerank_rpm: 100,
+            rerank_tpm: 2_000_000,
+        }
+    }
 }
 
 /// Internal structure for managing rate limits for a specific API.
@@ -26,13 +68,51 @@ struct ApiLimiter {
 impl RateLimiter {
     /// Creates a new `RateLimiter` instance with default limits.
     pub fn new() -> Self {
-        debug!("Creating new RateLimiter");
+        Self::with_limits(RateLimits::default())
+    }
+
+    /// Creates a new `RateLimiter` instance with the given limits.
+    pub fn with_limits(limits: RateLimits) -> Self {
+        debug!("Creating new RateLimiter with {:?}", limits);
         Self {
-            embeddings_limiter: Arc::new(Mutex::new(ApiLimiter::new(300, 1_000_000))),
-            reranking_limiter: Arc::new(Mutex::new(ApiLimiter::new(100, 2_000_000))),
+            embeddings_limiter: Arc::new(Mutex::new(ApiLimiter::new(
+                limits.embeddings_rpm,
+                limits.embeddings_tpm,
+            ))),
+            reranking_limiter: Arc::new(Mutex::new(ApiLimiter::new(
+                limits.rerank_rpm,
+                limits.rerank_tpm,
+            ))),
         }
     }
 
+    /// Checks if the limit for `endpoint` has been reached and returns how long to wait.
+    pub async fn check_limit(&self, endpoint: Endpoint, tokens: u32) -> Duration {
+        match endpoint {
+            Endpoint::Embeddings => self.check_embeddings_limit(tokens).await,
+            Endpoint::Rerank => self.check_reranking_limit(tokens).await,
+        }
+    }
+
+    /// Records `tokens` used against `endpoint`.
+    pub async fn update_usage(&self, endpoint: Endpoint, tokens: u32) {
+        match endpoint {
+            Endpoint::Embeddings => self.update_embeddings_usage(tokens).await,
+            Endpoint::Rerank => self.update_reranking_usage(tokens).await,
+        }
+    }
+
+    /// Returns the tokens used against `endpoint` in the last minute.
+    pub async fn tokens_used(&self, endpoint: Endpoint) -> u32 {
+        let limiter = match endpoint {
+            Endpoint::Embeddings => &self.embeddings_limiter,
+            Endpoint::Rerank => &self.reranking_limiter,
+        };
+        let mut limiter = limiter.lock().await;
+        limiter.clean_old_entries(Instant::now());
+        limiter.tokens.iter().map(|&(_, tokens)| tokens).sum()
+    }
+
     /// Checks if the embeddings API limit has been reached.
     ///
     /// # Arguments
@@ -0,0 +1,61 @@
+use std::io::{self, Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// Request bodies at least this large are gzipped by [`CompressionConfig::gzip`].
+pub const DEFAULT_GZIP_THRESHOLD: usize = 32 * 1024;
+
+/// Largest body [`gunzip`] decodes for the client; larger ones are rejected rather than
+/// held in memory.
+pub const MAX_DECOMPRESSED_BYTES: usize = 64 * 1024 * 1024;
+
+/// Gzip settings for request and response bodies. Both directions are off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// Gzip request bodies of at least this many bytes; `None` never compresses.
+    pub gzip_threshold: Option<usize>,
+    /// Send `Accept-Encoding: gzip` and decode gzipped responses.
+    pub accept_gzip: bool,
+}
+
+impl CompressionConfig {
+    /// Compresses request bodies of `threshold` bytes or more and accepts gzipped responses.
+    pub fn gzip(threshold: usize) -> Self {
+        Self {
+            gzip_threshold: Some(threshold),
+            accept_gzip: true,
+        }
+    }
+
+    /// Returns `true` if a request body of `len` bytes should be compressed.
+    pub fn should_compress(&self, len: usize) -> bool {
+        self.gzip_threshold
+            .is_some_and(|threshold| len >= threshold)
+    }
+}
+
+/// Gzips `data`.
+pub fn gzip(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 4), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Decodes gzipped `data`, failing with `InvalidData` if it decodes to more than `limit`
+/// bytes.
+pub fn gunzip(data: &[u8], limit: usize) -> io::Result<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(data.len().saturating_mul(4).min(limit));
+    // One byte past the limit is enough to tell that the body is too large.
+    GzDecoder::new(data)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decoded)?;
+    if decoded.len() > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("decompressed body exceeds {} bytes", limit),
+        ));
+    }
+    Ok(decoded)
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+/// The unique texts of a batch together with the mapping needed to fan
+/// results back out to the original positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupedInputs {
+    /// Unique texts, in order of first appearance.
+    pub unique: Vec<String>,
+    /// For every original input, the position of its text in `unique`.
+    pub positions: Vec<usize>,
+}
+
+impl DedupedInputs {
+    /// Returns the number of inputs that were collapsed into an earlier duplicate.
+    pub fn inputs_saved(&self) -> usize {
+        self.positions.len() - self.unique.len()
+    }
+
+    /// Returns true if at least one input was a duplicate.
+    pub fn has_duplicates(&self) -> bool {
+        self.inputs_saved() > 0
+    }
+
+    /// Expands one result per unique text into one result per original input.
+    pub fn fan_out<T: Clone>(&self, unique_results: &[T]) -> Vec<T> {
+        self.positions
+            .iter()
+            .map(|&position| unique_results[position].clone())
+            .collect()
+    }
+}
+
+/// Collapses identical texts in a batch, keeping the first occurrence of each.
+pub fn dedupe_inputs(texts: &[String]) -> DedupedInputs {
+    let mut seen: HashMap<&str, usize> = HashMap::with_capacity(texts.len());
+    let mut unique = Vec::new();
+    let mut positions = Vec::with_capacity(texts.len());
+
+    for text in texts {
+        let position = *seen.entry(text.as_str()).or_insert_with(|| {
+            unique.push(text.clone());
+            unique.len() - 1
+        });
+        positions.push(position);
+    }
+
+    DedupedInputs { unique, positions }
+}
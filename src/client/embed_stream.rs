@@ -0,0 +1,170 @@
+//! Streaming embeddings.
+//!
+//! [`embed_stream`] reads `(id, text)` pairs from any `Stream`, groups them into batches and
+//! embeds several batches at once with any [`EmbeddingsClient`]. At most `concurrency`
+//! batches are held in memory, so a database cursor can be piped into a vector store of any
+//! size. [`EmbeddingClient::embed_stream`] is a shorthand for the HTTP client.
+
+use std::sync::Arc;
+
+use futures::stream::{self, Stream, StreamExt};
+use log::debug;
+
+use crate::client::embeddings_client::{EmbeddingClient, EmbeddingsClient};
+use crate::errors::VoyageError;
+use crate::models::embeddings::{EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, InputType};
+
+/// Largest batch the embeddings endpoint accepts.
+pub const MAX_BATCH_SIZE: usize = 128;
+
+/// Outcome for one streamed input. Every input of a failed batch carries the same error.
+pub type StreamedEmbedding = Result<Vec<f32>, Arc<VoyageError>>;
+
+/// Batching and concurrency for [`embed_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbedStreamOptions {
+    /// Inputs per request, at most [`MAX_BATCH_SIZE`].
+    pub batch_size: usize,
+    /// Requests in flight at once.
+    pub concurrency: usize,
+    /// Yield results in input order. When `false`, batches are yielded as they finish.
+    pub ordered: bool,
+    /// Model to use instead of the configured embedding model.
+    pub model: Option<EmbeddingModel>,
+    pub input_type: Option<InputType>,
+}
+
+impl Default for EmbedStreamOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: MAX_BATCH_SIZE,
+            concurrency: 4,
+            ordered: true,
+            model: None,
+            input_type: None,
+        }
+    }
+}
+
+impl EmbedStreamOptions {
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    pub fn with_model(mut self, model: EmbeddingModel) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn with_input_type(mut self, input_type: InputType) -> Self {
+        self.input_type = Some(input_type);
+        self
+    }
+}
+
+impl EmbeddingClient {
+    /// Embeds a stream of `(id, text)` pairs with this client; see [`embed_stream`].
+    pub fn embed_stream<S, I>(
+        &self,
+        inputs: S,
+        options: EmbedStreamOptions,
+    ) -> impl Stream<Item = (I, StreamedEmbedding)>
+    where
+        S: Stream<Item = (I, String)>,
+    {
+        embed_stream(Arc::new(self.clone()), inputs, options)
+    }
+
+    /// Embeds a stream of texts, yielding each text's position in the stream with its
+    /// embedding.
+    pub fn embed_text_stream<S>(
+        &self,
+        texts: S,
+        options: EmbedStreamOptions,
+    ) -> impl Stream<Item = (usize, StreamedEmbedding)>
+    where
+        S: Stream<Item = String>,
+    {
+        self.embed_stream(texts.enumerate(), options)
+    }
+}
+
+/// Embeds a stream of `(id, text)` pairs with `client`, yielding `(id, embedding)` pairs.
+///
+/// Inputs are grouped into batches of `options.batch_size` and up to
+/// `options.concurrency` batches are requested at once, each going through the client's
+/// usual rate limiting, retries and circuit breaker. The input stream is only polled while
+/// fewer than that many batches are in flight.
+pub fn embed_stream<S, I>(
+    client: Arc<dyn EmbeddingsClient>,
+    inputs: S,
+    options: EmbedStreamOptions,
+) -> impl Stream<Item = (I, StreamedEmbedding)>
+where
+    S: Stream<Item = (I, String)>,
+{
+    let batch_size = options.batch_size.clamp(1, MAX_BATCH_SIZE);
+    let concurrency = options.concurrency.max(1);
+
+    let batches = inputs.chunks(batch_size).map(move |batch| {
+        let client = client.clone();
+        async move { embed_stream_batch(&*client, batch, options).await }
+    });
+    let results = if options.ordered {
+        batches.buffered(concurrency).left_stream()
+    } else {
+        batches.buffer_unordered(concurrency).right_stream()
+    };
+    results.flat_map(stream::iter)
+}
+
+async fn embed_stream_batch<I>(
+    client: &dyn EmbeddingsClient,
+    batch: Vec<(I, String)>,
+    options: EmbedStreamOptions,
+) -> Vec<(I, StreamedEmbedding)> {
+    let (ids, texts): (Vec<I>, Vec<String>) = batch.into_iter().unzip();
+    debug!("Embedding streamed batch of {} inputs", texts.len());
+    let expected = texts.len();
+    let request = EmbeddingsRequest {
+        input: EmbeddingsInput::Multiple(texts),
+        model: options.model.unwrap_or(client.embedding_model()),
+        input_type: options.input_type,
+        truncation: None,
+        encoding_format: None,
+    };
+
+    let result = client
+        .create_embedding(&request)
+        .await
+        .and_then(|mut response| {
+            if response.data.len() != expected {
+                return Err(VoyageError::JsonError(format!(
+                    "Expected {} embeddings, got {}",
+                    expected,
+                    response.data.len()
+                )));
+            }
+            response.data.sort_by_key(|d| d.index);
+            Ok(response.data.into_iter().map(|d| d.embedding))
+        });
+
+    match result {
+        Ok(embeddings) => ids.into_iter().zip(embeddings.map(Ok)).collect(),
+        Err(error) => {
+            let error = Arc::new(error);
+            ids.into_iter().map(|id| (id, Err(error.clone()))).collect()
+        }
+    }
+}
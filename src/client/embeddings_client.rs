@@ -1,24 +1,92 @@
+use crate::client::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::client::client_limiter::Endpoint;
+use crate::client::dedupe::dedupe_inputs;
+use crate::client::metrics::ClientMetrics;
+use crate::client::telemetry::OperationSpan;
+use crate::client::transport::{error_for_status, HttpTransport, DEFAULT_BASE_URL};
 use crate::client::RateLimiter;
 use crate::config::VoyageConfig;
 use crate::models::embeddings::{
-    EmbeddingData, EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse,
+    DedupeStats, EmbeddingData, EmbeddingModel, EmbeddingsInput, EmbeddingsRequest,
+    EmbeddingsResponse,
 };
+use crate::models::usage::estimate_tokens;
 use crate::VoyageError;
 
-use log::{debug, info, warn};
-use reqwest::Client;
+use async_trait::async_trait;
+use log::{debug, warn};
 use std::sync::Arc;
-use tokio::time::sleep;
 
 /// Base URL for the Voyage AI API.
-pub const BASE_URL: &str = "https://api.voyageai.com/v1";
+pub const BASE_URL: &str = DEFAULT_BASE_URL;
+
+/// Client trait for creating embeddings, so that search and other generic code can run
+/// against a stub instead of the HTTP API.
+#[async_trait]
+pub trait EmbeddingsClient: std::fmt::Debug + Send + Sync {
+    /// Model used for requests the caller does not build itself, e.g. through
+    /// [`Embedder`](crate::traits::llm::Embedder).
+    fn embedding_model(&self) -> EmbeddingModel;
+
+    /// Name of the model that actually serves requests. Backends for other providers
+    /// override this along with [`embedding_dimension`](Self::embedding_dimension).
+    fn embedding_model_name(&self) -> &str {
+        self.embedding_model().as_str()
+    }
+
+    /// Length of the vectors returned.
+    fn embedding_dimension(&self) -> usize {
+        self.embedding_model().embedding_dimension()
+    }
+
+    /// Creates embeddings for the given request.
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError>;
+}
+
+#[async_trait]
+impl EmbeddingsClient for EmbeddingClient {
+    fn embedding_model(&self) -> EmbeddingModel {
+        self.config.embedding_model
+    }
+
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        EmbeddingClient::create_embedding(self, request).await
+    }
+}
+
+#[async_trait]
+impl<T: EmbeddingsClient + ?Sized> EmbeddingsClient for Arc<T> {
+    fn embedding_model(&self) -> EmbeddingModel {
+        (**self).embedding_model()
+    }
+
+    fn embedding_model_name(&self) -> &str {
+        (**self).embedding_model_name()
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        (**self).embedding_dimension()
+    }
+
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        (**self).create_embedding(request).await
+    }
+}
 
 /// Client for interacting with the Voyage AI embeddings API.
 #[derive(Debug, Clone)]
 pub struct EmbeddingClient {
-    client: Client,
+    transport: HttpTransport,
     config: VoyageConfig,
-    rate_limiter: Arc<RateLimiter>,
 }
 
 impl EmbeddingClient {
@@ -54,56 +122,152 @@ impl EmbeddingClient {
 
 impl EmbeddingClient {
     /// Creates a new `EmbeddingClient` instance.
-    pub fn new(config: VoyageConfig, rate_limiter: Arc<RateLimiter>) -> Self {
+    pub fn new(config: VoyageConfig, rate_limiter: Arc<RateLimiter>) -> Result<Self, VoyageError> {
         debug!("Creating new EmbeddingClient");
-        Self {
-            client: Client::new(),
+        Ok(Self {
+            transport: HttpTransport::new(config.clone(), rate_limiter)?,
             config,
-            rate_limiter,
-        }
+        })
+    }
+
+    pub(crate) fn config(&self) -> &VoyageConfig {
+        &self.config
+    }
+
+    /// Returns the traffic counters for this client.
+    pub fn metrics(&self) -> &ClientMetrics {
+        self.transport.metrics()
+    }
+
+    /// Returns the state of the endpoint's circuit breaker, if one is configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.transport
+            .circuit_breaker(Endpoint::Embeddings)
+            .map(CircuitBreaker::state)
     }
 
     /// Creates embeddings for the given request.
+    ///
+    /// When `dedupe_inputs` is enabled in the config, identical texts in a batch are
+    /// sent once and their embedding is copied back to every original index. When
+    /// `split_long_inputs` is set, inputs longer than its window are embedded in windows
+    /// and pooled, and listed in the response's `split`. The config's `cache` is applied
+    /// by [`VoyageAiClient`](crate::VoyageAiClient), not here; see
+    /// [`CachedEmbeddingsClient`](crate::client::cache::CachedEmbeddingsClient).
     pub async fn create_embedding(
         &self,
         request: &EmbeddingsRequest,
     ) -> Result<EmbeddingsResponse, VoyageError> {
-        let url = format!("{}/embeddings", BASE_URL);
-        debug!("Creating embedding with URL: {}", url);
+        if let Some(split) = &self.config.split_long_inputs {
+            if let Some(result) = self.create_split_embedding(request, split).await {
+                return result;
+            }
+        }
+        self.create_unsplit_embedding(request).await
+    }
+
+    pub(crate) async fn create_unsplit_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        let texts = match &request.input {
+            EmbeddingsInput::Multiple(texts) if self.config.dedupe_inputs => texts,
+            _ => return self.send_embedding_request(request).await,
+        };
+
+        let deduped = dedupe_inputs(texts);
+        if !deduped.has_duplicates() {
+            return self.send_embedding_request(request).await;
+        }
+
+        let tokens_saved =
+            Self::estimate_text_tokens(texts) - Self::estimate_text_tokens(&deduped.unique);
+        debug!(
+            "Deduplicated embedding batch: {} inputs, {} unique",
+            texts.len(),
+            deduped.unique.len()
+        );
+
+        let unique_request = EmbeddingsRequest {
+            input: EmbeddingsInput::Multiple(deduped.unique.clone()),
+            model: request.model,
+            input_type: request.input_type,
+            truncation: request.truncation,
+            encoding_format: request.encoding_format,
+        };
+        let mut response = self.send_embedding_request(&unique_request).await?;
+
+        response.data.sort_by_key(|d| d.index);
+        let unique_embeddings: Vec<Vec<f32>> =
+            response.data.into_iter().map(|d| d.embedding).collect();
+        if unique_embeddings.len() != deduped.unique.len() {
+            return Err(VoyageError::JsonError(format!(
+                "Expected {} embeddings, got {}",
+                deduped.unique.len(),
+                unique_embeddings.len()
+            )));
+        }
+
+        response.data = deduped
+            .fan_out(&unique_embeddings)
+            .into_iter()
+            .enumerate()
+            .map(|(index, embedding)| EmbeddingData {
+                object: "embedding".to_string(),
+                embedding,
+                index,
+            })
+            .collect();
+        response.dedupe = Some(DedupeStats {
+            inputs_saved: deduped.inputs_saved(),
+            tokens_saved,
+        });
+
+        Ok(response)
+    }
+
+    async fn send_embedding_request(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        debug!(
+            "Creating embedding with URL: {}",
+            self.transport.url("/embeddings")
+        );
 
         let estimated_tokens = self.estimate_tokens(request);
         debug!("Estimated tokens for request: {}", estimated_tokens);
 
-        let wait_time = self
-            .rate_limiter
-            .check_embeddings_limit(estimated_tokens)
+        let input_count = match &request.input {
+            EmbeddingsInput::Single(_) => 1,
+            EmbeddingsInput::Multiple(texts) => texts.len(),
+        };
+        let span = OperationSpan::embeddings(request.model.as_str(), input_count, estimated_tokens);
+        let result = span
+            .instrument(self.execute_embedding_request(request, estimated_tokens, &span))
             .await;
-        if wait_time.as_secs() > 0 {
-            info!(
-                "Rate limit reached. Waiting for {} seconds",
-                wait_time.as_secs()
-            );
-            sleep(wait_time).await;
-        }
+        span.finish(&result);
+        result
+    }
 
+    async fn execute_embedding_request(
+        &self,
+        request: &EmbeddingsRequest,
+        estimated_tokens: u32,
+        span: &OperationSpan,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
         debug!("Sending embedding request");
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(self.config.api_key())
-            .json(&request)
-            .send()
+        let (response, key, metadata) = self
+            .transport
+            .call(Endpoint::Embeddings, request, estimated_tokens)
             .await?;
+        span.record_status(response.status.as_u16());
 
-        let status = response.status();
-        let text = response.text().await?;
-
-        
-
-        match status {
+        match response.status {
             reqwest::StatusCode::OK => {
                 debug!("Embedding request successful");
-                let embeddings_response: EmbeddingsResponse = serde_json::from_str(&text)?;
+                let embeddings_response: EmbeddingsResponse = serde_json::from_str(&response.body)?;
+                span.record_tokens(embeddings_response.usage.total_tokens);
 
                 let embeddings_response = if embeddings_response.data.is_empty() {
                     EmbeddingsResponse {
@@ -118,23 +282,19 @@ impl EmbeddingClient {
                     embeddings_response
                 };
 
-                self.rate_limiter
-                    .update_embeddings_usage(embeddings_response.usage.total_tokens)
+                key.limiter()
+                    .update_usage(Endpoint::Embeddings, embeddings_response.usage.total_tokens)
                     .await;
 
-                Ok(embeddings_response)
-            }
-            reqwest::StatusCode::UNAUTHORIZED => {
-                warn!("Unauthorized: Invalid API key");
-                Err(VoyageError::Unauthorized)
-            }
-            reqwest::StatusCode::FORBIDDEN => {
-                warn!("Forbidden: {}", text);
-                Err(VoyageError::Forbidden(text))
+                Ok(EmbeddingsResponse {
+                    served_by: Some(key.label().to_string()),
+                    metadata,
+                    ..embeddings_response
+                })
             }
-            _ => {
+            status => {
                 warn!("Embedding request failed with status: {}", status);
-                Err(VoyageError::ApiError(status, text))
+                Err(error_for_status(&response))
             }
         }
     }
@@ -143,19 +303,21 @@ impl EmbeddingClient {
     fn estimate_tokens(&self, request: &EmbeddingsRequest) -> u32 {
         match &request.input {
             EmbeddingsInput::Single(text) => {
-                // Rough estimate: 1 token per 4 characters + small overhead
-                let base_tokens = (text.len() as f32 / 4.0).ceil() as u32;
-                base_tokens + 2
-            }
-            EmbeddingsInput::Multiple(texts) => {
-                // Calculate tokens for each text and sum
-                let mut total = 0;
-                for text in texts {
-                    total += (text.len() as f32 / 4.0).ceil() as u32;
-                }
-                // Add overhead for batch processing
-                total + (2 * texts.len() as u32)
+                // Rough estimate plus a small overhead
+                estimate_tokens(text) as u32 + 2
             }
+            EmbeddingsInput::Multiple(texts) => Self::estimate_text_tokens(texts),
+        }
+    }
+
+    /// Estimates the tokens for a batch of texts, including per-text batch overhead.
+    pub(crate) fn estimate_text_tokens(texts: &[String]) -> u32 {
+        // Calculate tokens for each text and sum
+        let mut total = 0;
+        for text in texts {
+            total += estimate_tokens(text) as u32;
         }
+        // Add overhead for batch processing
+        total + (2 * texts.len() as u32)
     }
 }
@@ -0,0 +1,85 @@
+//! Request/response hooks that run around every API call.
+//!
+//! Interceptors are registered on [`VoyageBuilder`](crate::VoyageBuilder) and run by the
+//! shared transport, so they apply to embeddings, rerank and any later endpoint alike.
+
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+use crate::client::transport::RawResponse;
+use crate::errors::VoyageError;
+
+/// A request on its way to the API, as seen by interceptors.
+#[derive(Debug, Clone)]
+pub struct InterceptedRequest {
+    /// HTTP method, e.g. `POST`.
+    pub method: String,
+    /// Endpoint path such as `/embeddings`.
+    pub path: String,
+    /// Extra headers sent with the request.
+    pub headers: HeaderMap,
+    /// JSON body; changes made here are what gets sent.
+    pub body: Value,
+    started: Instant,
+}
+
+impl InterceptedRequest {
+    /// Creates a request with no extra headers.
+    pub fn new(method: impl Into<String>, path: impl Into<String>, body: Value) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+            headers: HeaderMap::new(),
+            body,
+            started: Instant::now(),
+        }
+    }
+
+    /// Time since the request entered the interceptor chain.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// What the transport should do after a `before_request` hook.
+#[derive(Debug, Clone)]
+pub enum InterceptAction {
+    /// Carry on with the next interceptor, then the API call.
+    Continue,
+    /// Skip the remaining `before_request` hooks and the API call, using this response instead.
+    Respond(RawResponse),
+}
+
+/// Hooks run around every API request.
+///
+/// All hooks have no-op defaults, so implementors only override what they need.
+/// Interceptors run in the order they were registered.
+#[async_trait]
+pub trait Interceptor: Debug + Send + Sync {
+    /// Called before the request is sent. May edit the request, answer it directly with
+    /// [`InterceptAction::Respond`], or abort it by returning an error.
+    async fn before_request(
+        &self,
+        _request: &mut InterceptedRequest,
+    ) -> Result<InterceptAction, VoyageError> {
+        Ok(InterceptAction::Continue)
+    }
+
+    /// Called with the response, including short-circuited ones. May edit the response
+    /// or turn it into an error.
+    async fn after_response(
+        &self,
+        _request: &InterceptedRequest,
+        _response: &mut RawResponse,
+    ) -> Result<(), VoyageError> {
+        Ok(())
+    }
+
+    /// Called when the request fails: a transport error, an error returned by a hook,
+    /// or a non-success status.
+    async fn on_error(&self, _request: &InterceptedRequest, _error: &VoyageError) {}
+}
@@ -0,0 +1,113 @@
+//! Per-item failure isolation for embedding batches.
+//!
+//! The embeddings endpoint rejects a whole batch with a 400 when any one input is
+//! invalid. [`create_embedding_isolated`] bisects such a batch until every rejected input
+//! stands alone, so the rest of the batch still gets embedded. It works with any
+//! [`EmbeddingsClient`]; [`EmbeddingClient::create_embedding_isolated`] is a shorthand.
+
+use log::debug;
+
+use crate::client::embeddings_client::{EmbeddingClient, EmbeddingsClient};
+use crate::errors::VoyageError;
+use crate::models::embeddings::{EmbeddingsInput, EmbeddingsRequest};
+
+/// Embedding or error for one input of an isolated batch.
+pub type ItemResult = Result<Vec<f32>, VoyageError>;
+
+/// Outcome of [`create_embedding_isolated`].
+#[derive(Debug)]
+pub struct IsolatedBatch {
+    /// One result per input, in input order. Rejected inputs carry the 400 they got
+    /// when sent on their own.
+    pub items: Vec<ItemResult>,
+    /// Tokens billed for the accepted inputs.
+    pub total_tokens: u64,
+    /// Requests sent, including the bisection steps.
+    pub requests: usize,
+}
+
+impl IsolatedBatch {
+    /// Number of rejected inputs.
+    pub fn failures(&self) -> usize {
+        self.items.iter().filter(|item| item.is_err()).count()
+    }
+}
+
+impl EmbeddingClient {
+    /// Embeds a batch, isolating inputs the API rejects; see [`create_embedding_isolated`].
+    pub async fn create_embedding_isolated(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<IsolatedBatch, VoyageError> {
+        create_embedding_isolated(self, request).await
+    }
+}
+
+/// Embeds a batch with `client`, isolating inputs it rejects.
+///
+/// When the batch is rejected with a 400, it is split in half and each half is sent
+/// again, recursively, until the offending inputs are sent alone. A batch of `n`
+/// inputs with `k` bad ones costs about `2k·log2(n)` extra requests. Errors other
+/// than 400 (auth, rate limits after retries, outages) fail the whole call.
+pub async fn create_embedding_isolated<C: EmbeddingsClient + ?Sized>(
+    client: &C,
+    request: &EmbeddingsRequest,
+) -> Result<IsolatedBatch, VoyageError> {
+    let texts = match &request.input {
+        EmbeddingsInput::Single(text) => vec![text.clone()],
+        EmbeddingsInput::Multiple(texts) => texts.clone(),
+    };
+    let mut items: Vec<Option<ItemResult>> = texts.iter().map(|_| None).collect();
+    let mut total_tokens = 0;
+    let mut requests = 0;
+    let mut pending = if texts.is_empty() {
+        Vec::new()
+    } else {
+        vec![(0, texts.len())]
+    };
+
+    while let Some((start, end)) = pending.pop() {
+        requests += 1;
+        let part = EmbeddingsRequest {
+            input: EmbeddingsInput::Multiple(texts[start..end].to_vec()),
+            model: request.model,
+            input_type: request.input_type,
+            truncation: request.truncation,
+            encoding_format: request.encoding_format,
+        };
+        match client.create_embedding(&part).await {
+            Ok(mut response) => {
+                if response.data.len() != end - start {
+                    return Err(VoyageError::JsonError(format!(
+                        "Expected {} embeddings, got {}",
+                        end - start,
+                        response.data.len()
+                    )));
+                }
+                response.data.sort_by_key(|d| d.index);
+                total_tokens += response.usage.total_tokens as u64;
+                for (slot, data) in items[start..end].iter_mut().zip(response.data) {
+                    *slot = Some(Ok(data.embedding));
+                }
+            }
+            Err(error @ VoyageError::BadRequest { .. }) if end - start == 1 => {
+                debug!("Input {} rejected: {}", start, error);
+                items[start] = Some(Err(error));
+            }
+            Err(VoyageError::BadRequest { .. }) => {
+                let middle = start + (end - start) / 2;
+                debug!("Bisecting rejected inputs {}..{} at {}", start, end, middle);
+                // Pushed in reverse so the lower half is sent first.
+                pending.push((middle, end));
+                pending.push((start, middle));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(IsolatedBatch {
+        items: items.into_iter().flatten().collect(),
+        total_tokens,
+        requests,
+    })
+}
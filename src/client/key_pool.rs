@@ -0,0 +1,324 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use log::{info, warn};
+use serde::Deserialize;
+
+use crate::client::client_limiter::{Endpoint, RateLimiter, RateLimits};
+use crate::config::ApiKey;
+use crate::errors::VoyageError;
+
+/// How a [`KeyPool`] picks the key for each request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySelection {
+    /// Cycles through the keys in order.
+    #[default]
+    RoundRobin,
+    /// Picks the key that has used the fewest tokens on the endpoint in the last minute.
+    LeastUsed,
+    /// Uses the first key until it is rate limited (429), then the next, and so on.
+    Failover,
+}
+
+/// A set of API keys, each with its own rate-limiter state.
+///
+/// Whatever the selection strategy, a key answered with 401 is quarantined and the
+/// request moves on to another key. Clones share keys, limiter state and quarantine.
+#[derive(Debug, Clone)]
+pub struct KeyPool {
+    inner: Arc<PoolInner>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    keys: Vec<PooledKey>,
+    selection: KeySelection,
+    next: AtomicUsize,
+    /// The key failover selection uses until it is rate limited.
+    active: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct PooledKey {
+    key: ApiKey,
+    label: String,
+    /// Set when the key is first used, unless the pool was given limits up front.
+    limiter: OnceLock<Arc<RateLimiter>>,
+    quarantined: AtomicBool,
+}
+
+impl PooledKey {
+    fn limiter(&self) -> &Arc<RateLimiter> {
+        self.limiter.get_or_init(|| Arc::new(RateLimiter::new()))
+    }
+}
+
+/// The key chosen for one request.
+#[derive(Debug, Clone)]
+pub struct ServingKey {
+    index: usize,
+    label: String,
+    key: ApiKey,
+    limiter: Arc<RateLimiter>,
+}
+
+impl ServingKey {
+    /// Position of the key in its pool.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Redacted label for the key, safe to log, e.g. `#1 …9f2c`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The key itself.
+    pub fn api_key(&self) -> &ApiKey {
+        &self.key
+    }
+
+    /// The rate limiter tracking this key's quota.
+    pub fn limiter(&self) -> &Arc<RateLimiter> {
+        &self.limiter
+    }
+}
+
+impl KeyPool {
+    /// Creates a pool of `keys`, each limited by the `rate_limits` of the config the pool
+    /// is used with, or by the default [`RateLimits`] outside a client.
+    ///
+    /// Fails with `VoyageError::MissingApiKey` if `keys` is empty or any key is blank.
+    pub fn new<I, K>(keys: I, selection: KeySelection) -> Result<Self, VoyageError>
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<ApiKey>,
+    {
+        let keys = checked_keys(keys)?
+            .into_iter()
+            .map(|key| (key, None))
+            .collect();
+        Ok(Self::from_keys(keys, selection))
+    }
+
+    /// Creates a pool of `keys`, each limited by `limits`.
+    pub fn with_limits<I, K>(
+        keys: I,
+        selection: KeySelection,
+        limits: RateLimits,
+    ) -> Result<Self, VoyageError>
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<ApiKey>,
+    {
+        let keys = checked_keys(keys)?
+            .into_iter()
+            .map(|key| (key, Some(Arc::new(RateLimiter::with_limits(limits)))))
+            .collect();
+        Ok(Self::from_keys(keys, selection))
+    }
+
+    /// A pool of one key that uses an existing rate limiter.
+    pub(crate) fn single(key: ApiKey, limiter: Arc<RateLimiter>) -> Self {
+        Self::from_keys(vec![(key, Some(limiter))], KeySelection::Failover)
+    }
+
+    /// Gives keys created without limits a limiter enforcing `limits`. Keys that already
+    /// have one, including every key once the pool has served a request, are unchanged.
+    pub(crate) fn init_limits(&self, limits: RateLimits) {
+        for key in &self.inner.keys {
+            key.limiter
+                .get_or_init(|| Arc::new(RateLimiter::with_limits(limits)));
+        }
+    }
+
+    fn from_keys(keys: Vec<(ApiKey, Option<Arc<RateLimiter>>)>, selection: KeySelection) -> Self {
+        let keys = keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, limiter))| PooledKey {
+                label: format!("#{} {}", index, key.hint()),
+                key,
+                limiter: limiter.map(OnceLock::from).unwrap_or_default(),
+                quarantined: AtomicBool::new(false),
+            })
+            .collect();
+        Self {
+            inner: Arc::new(PoolInner {
+                keys,
+                selection,
+                next: AtomicUsize::new(0),
+                active: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Returns the selection strategy.
+    pub fn selection(&self) -> KeySelection {
+        self.inner.selection
+    }
+
+    /// Returns the number of keys, including quarantined ones.
+    pub fn len(&self) -> usize {
+        self.inner.keys.len()
+    }
+
+    /// Returns `true` if the pool has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.inner.keys.is_empty()
+    }
+
+    /// Returns the first key, used where a single key is needed.
+    pub fn primary(&self) -> &ApiKey {
+        &self.inner.keys[0].key
+    }
+
+    /// Returns the redacted labels of quarantined keys.
+    pub fn quarantined(&self) -> Vec<String> {
+        self.inner
+            .keys
+            .iter()
+            .filter(|k| k.quarantined.load(Ordering::SeqCst))
+            .map(|k| k.label.clone())
+            .collect()
+    }
+
+    /// Returns every quarantined key to service.
+    pub fn reinstate_all(&self) {
+        for key in &self.inner.keys {
+            key.quarantined.store(false, Ordering::SeqCst);
+        }
+    }
+
+    /// Takes `key` out of service after a 401. The last key in service is never
+    /// quarantined, so a pool whose keys are all rejected keeps reporting the 401.
+    pub fn quarantine(&self, key: &ServingKey) {
+        let in_service = self
+            .inner
+            .keys
+            .iter()
+            .filter(|k| !k.quarantined.load(Ordering::SeqCst))
+            .count();
+        if in_service < 2 {
+            return;
+        }
+        if !self.inner.keys[key.index]
+            .quarantined
+            .swap(true, Ordering::SeqCst)
+        {
+            warn!(
+                "API key {} was rejected (401) and is quarantined",
+                key.label
+            );
+        }
+    }
+
+    /// Picks a key for `endpoint`, skipping quarantined keys and those in `exclude`.
+    ///
+    /// Returns `None` if the only keys left in service are excluded.
+    pub async fn select(
+        &self,
+        endpoint: Endpoint,
+        exclude: &[usize],
+    ) -> Result<Option<ServingKey>, VoyageError> {
+        let keys = &self.inner.keys;
+        let eligible = self.eligible(exclude)?;
+        if eligible.is_empty() {
+            return Ok(None);
+        }
+
+        let index = match self.inner.selection {
+            KeySelection::Failover => {
+                let active = self.inner.active.load(Ordering::SeqCst);
+                *eligible
+                    .iter()
+                    .find(|&&i| i >= active)
+                    .unwrap_or(&eligible[0])
+            }
+            KeySelection::RoundRobin => {
+                let start = self.inner.next.fetch_add(1, Ordering::SeqCst) % keys.len();
+                *eligible
+                    .iter()
+                    .find(|&&i| i >= start)
+                    .unwrap_or(&eligible[0])
+            }
+            KeySelection::LeastUsed => {
+                let mut best = (eligible[0], u32::MAX);
+                for &i in &eligible {
+                    let used = keys[i].limiter().tokens_used(endpoint).await;
+                    if used < best.1 {
+                        best = (i, used);
+                    }
+                }
+                best.0
+            }
+        };
+
+        let key = &keys[index];
+        if !exclude.is_empty() {
+            info!("Failing over to API key {}", key.label);
+        }
+        Ok(Some(ServingKey {
+            index,
+            label: key.label.clone(),
+            key: key.key.clone(),
+            limiter: key.limiter().clone(),
+        }))
+    }
+
+    /// Returns `true` if a request sent with `key` that gets a 429 can move on to
+    /// another key, i.e. the pool fails over and has a key in service outside `tried`.
+    pub(crate) fn can_fail_over(&self, key: &ServingKey, tried: &[usize]) -> bool {
+        self.inner.selection == KeySelection::Failover
+            && self
+                .eligible(tried)
+                .is_ok_and(|eligible| eligible.iter().any(|&i| i != key.index))
+    }
+
+    /// Records that `key` was rate limited (429). Failover selection then sticks to the
+    /// next key until that one is rate limited in turn.
+    pub(crate) fn rate_limited(&self, key: &ServingKey) {
+        if self.inner.selection != KeySelection::Failover {
+            return;
+        }
+        let next = (key.index + 1) % self.inner.keys.len();
+        if self
+            .inner
+            .active
+            .compare_exchange(key.index, next, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            info!("API key {} was rate limited; failing over", key.label);
+        }
+    }
+
+    /// Returns the keys in service outside `exclude`, or `VoyageError::Unauthorized` if
+    /// every key is quarantined.
+    fn eligible(&self, exclude: &[usize]) -> Result<Vec<usize>, VoyageError> {
+        let keys = &self.inner.keys;
+        let available: Vec<usize> = (0..keys.len())
+            .filter(|&i| !keys[i].quarantined.load(Ordering::SeqCst))
+            .collect();
+        if available.is_empty() {
+            return Err(VoyageError::Unauthorized);
+        }
+        Ok(available
+            .into_iter()
+            .filter(|i| !exclude.contains(i))
+            .collect())
+    }
+}
+
+/// Collects `keys`, failing with `VoyageError::MissingApiKey` if there are none or any is blank.
+fn checked_keys<I, K>(keys: I) -> Result<Vec<ApiKey>, VoyageError>
+where
+    I: IntoIterator<Item = K>,
+    K: Into<ApiKey>,
+{
+    let keys: Vec<ApiKey> = keys.into_iter().map(Into::into).collect();
+    if keys.is_empty() || keys.iter().any(|key| key.expose().trim().is_empty()) {
+        return Err(VoyageError::MissingApiKey);
+    }
+    Ok(keys)
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Counters for the HTTP traffic of a client.
+///
+/// Clones share counters, so the embeddings and rerank clients built from one
+/// [`VoyageConfig`](crate::config::VoyageConfig) report into the same totals.
+#[derive(Debug, Clone, Default)]
+pub struct ClientMetrics {
+    inner: Arc<Counters>,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicU64,
+    compressed_requests: AtomicU64,
+    bytes_sent: AtomicU64,
+    uncompressed_bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    uncompressed_bytes_received: AtomicU64,
+}
+
+/// A point-in-time copy of [`ClientMetrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// HTTP requests sent, retries included.
+    pub requests: u64,
+    /// Requests whose body was gzipped.
+    pub compressed_requests: u64,
+    /// Request body bytes as sent on the wire.
+    pub bytes_sent: u64,
+    /// Request body bytes before compression.
+    pub uncompressed_bytes_sent: u64,
+    /// Response body bytes as received on the wire.
+    pub bytes_received: u64,
+    /// Response body bytes after decompression.
+    pub uncompressed_bytes_received: u64,
+}
+
+impl ClientMetrics {
+    /// Returns the current totals.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let c = &self.inner;
+        MetricsSnapshot {
+            requests: c.requests.load(Ordering::Relaxed),
+            compressed_requests: c.compressed_requests.load(Ordering::Relaxed),
+            bytes_sent: c.bytes_sent.load(Ordering::Relaxed),
+            uncompressed_bytes_sent: c.uncompressed_bytes_sent.load(Ordering::Relaxed),
+            bytes_received: c.bytes_received.load(Ordering::Relaxed),
+            uncompressed_bytes_received: c.uncompressed_bytes_received.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets every counter back to zero.
+    pub fn reset(&self) {
+        let c = &self.inner;
+        for counter in [
+            &c.requests,
+            &c.compressed_requests,
+            &c.bytes_sent,
+            &c.uncompressed_bytes_sent,
+            &c.bytes_received,
+            &c.uncompressed_bytes_received,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_request(
+        &self,
+        wire_bytes: usize,
+        uncompressed_bytes: usize,
+        gzipped: bool,
+    ) {
+        let c = &self.inner;
+        c.requests.fetch_add(1, Ordering::Relaxed);
+        if gzipped {
+            c.compressed_requests.fetch_add(1, Ordering::Relaxed);
+        }
+        c.bytes_sent.fetch_add(wire_bytes as u64, Ordering::Relaxed);
+        c.uncompressed_bytes_sent
+            .fetch_add(uncompressed_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_response(&self, wire_bytes: usize, uncompressed_bytes: usize) {
+        let c = &self.inner;
+        c.bytes_received
+            .fetch_add(wire_bytes as u64, Ordering::Relaxed);
+        c.uncompressed_bytes_received
+            .fetch_add(uncompressed_bytes as u64, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::debug;
+
+use crate::client::embeddings_client::{EmbeddingClient, EmbeddingsClient};
+use crate::client::rerank_client::RerankClient;
+use crate::errors::VoyageError;
+use crate::models::embeddings::{
+    EmbeddingData, EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse,
+    InputType, Usage,
+};
+use crate::models::hashing::fnv1a;
+use crate::models::metadata::ResponseMetadata;
+use crate::models::rerank::{self, RerankModel, RerankRequest, RerankResponse, RerankResult};
+use crate::traits::llm::{finish_rerank, Embedder, RerankOptions, Reranker};
+
+/// Default seed used by [`MockVoyageClient::new`].
+pub const DEFAULT_MOCK_SEED: u64 = 0x5eed_cafe;
+
+/// A failure the mock client should return instead of a result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockFailure {
+    /// Fails with `VoyageError::RateLimitExceeded`, as a 429 would.
+    RateLimited(Duration),
+    /// Fails with `VoyageError::InternalServerError`, as a 500 would.
+    ServerError(String),
+    /// Fails with `VoyageError::Timeout`.
+    Timeout,
+}
+
+impl From<MockFailure> for VoyageError {
+    fn from(failure: MockFailure) -> Self {
+        match failure {
+            MockFailure::RateLimited(reset_in) => VoyageError::RateLimitExceeded { reset_in },
+            MockFailure::ServerError(message) => VoyageError::InternalServerError { message },
+            MockFailure::Timeout => VoyageError::Timeout,
+        }
+    }
+}
+
+/// Deterministic, offline stand-in for `VoyageAiClient`.
+///
+/// Embeddings are derived from the words of each text, so identical texts always
+/// produce identical vectors and texts sharing words are closer than unrelated ones.
+/// Rerank scores are the fraction of query words found in each document.
+#[derive(Debug, Clone)]
+pub struct MockVoyageClient {
+    embedding_model: EmbeddingModel,
+    rerank_model: RerankModel,
+    seed: u64,
+    failures: Arc<Mutex<VecDeque<MockFailure>>>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl Default for MockVoyageClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockVoyageClient {
+    /// Creates a mock client using the default models and seed.
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_MOCK_SEED)
+    }
+
+    /// Creates a mock client whose embeddings are derived from `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        debug!("Creating new MockVoyageClient with seed {}", seed);
+        Self {
+            embedding_model: EmbeddingModel::default(),
+            rerank_model: RerankModel::default(),
+            seed,
+            failures: Arc::new(Mutex::new(VecDeque::new())),
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Sets the model used by the convenience embedding methods.
+    pub fn with_embedding_model(mut self, model: EmbeddingModel) -> Self {
+        self.embedding_model = model;
+        self
+    }
+
+    /// Sets the model used by the convenience rerank methods.
+    pub fn with_rerank_model(mut self, model: RerankModel) -> Self {
+        self.rerank_model = model;
+        self
+    }
+
+    /// Queues a failure to be returned by the next call, after any already queued.
+    pub fn fail_next(&self, failure: MockFailure) -> &Self {
+        self.failures
+            .lock()
+            .expect("mock failure queue poisoned")
+            .push_back(failure);
+        self
+    }
+
+    /// Returns the number of embeddings and rerank calls made so far, including failed ones.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Returns the embedding the mock produces for `text` with `model`.
+    pub fn embedding_for(&self, text: &str, model: EmbeddingModel) -> Vec<f32> {
+        self.embedding_with_dimension(text, model.embedding_dimension())
+    }
+
+    /// Returns the embedding the mock produces for `text`, with `dimension` values.
+    pub fn embedding_with_dimension(&self, text: &str, dimension: usize) -> Vec<f32> {
+        let dimension = dimension.max(1);
+        let mut vector = vec![0.0_f32; dimension];
+
+        let words = words(text);
+        let features: Vec<&str> = if words.is_empty() {
+            vec![text]
+        } else {
+            words.iter().map(String::as_str).collect()
+        };
+
+        for feature in features {
+            let hash = fnv1a(self.seed, feature.as_bytes());
+            let slot = (hash % dimension as u64) as usize;
+            let sign = if (hash >> 63) == 0 { 1.0 } else { -1.0 };
+            vector[slot] += sign;
+        }
+
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|x| *x /= norm);
+        }
+        vector
+    }
+
+    /// Returns the lexical-overlap score the mock assigns to `document` for `query`.
+    pub fn relevance_score(query: &str, document: &str) -> f64 {
+        let query_words: HashSet<String> = words(query).into_iter().collect();
+        if query_words.is_empty() {
+            return 0.0;
+        }
+        let document_words: HashSet<String> = words(document).into_iter().collect();
+        let overlap = query_words.intersection(&document_words).count();
+        overlap as f64 / query_words.len() as f64
+    }
+
+    /// Creates embeddings for the given request, like `EmbeddingClient::create_embedding`.
+    pub async fn embeddings(
+        &self,
+        request: EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        self.record_call()?;
+
+        let texts = match request.input {
+            EmbeddingsInput::Single(text) => vec![text],
+            EmbeddingsInput::Multiple(texts) => texts,
+        };
+
+        let data = texts
+            .iter()
+            .enumerate()
+            .map(|(index, text)| EmbeddingData {
+                object: "embedding".to_string(),
+                embedding: self.embedding_for(text, request.model),
+                index,
+            })
+            .collect();
+
+        Ok(EmbeddingsResponse {
+            object: "list".to_string(),
+            data,
+            model: request.model.as_str().to_string(),
+            usage: Usage {
+                total_tokens: EmbeddingClient::estimate_text_tokens(&texts),
+            },
+            dedupe: None,
+            split: Vec::new(),
+            served_by: None,
+            metadata: ResponseMetadata::default(),
+        })
+    }
+
+    /// Reranks documents for the given request, like `DefaultRerankClient::rerank`.
+    pub async fn rerank(&self, request: RerankRequest) -> Result<RerankResponse, VoyageError> {
+        self.record_call()?;
+
+        let mut data: Vec<RerankResult> = request
+            .documents
+            .iter()
+            .enumerate()
+            .map(|(index, document)| RerankResult {
+                relevance_score: Self::relevance_score(&request.query, document),
+                index,
+                document: Some(document.clone()),
+            })
+            .collect();
+
+        data.sort_by(|a, b| {
+            b.relevance_score
+                .total_cmp(&a.relevance_score)
+                .then(a.index.cmp(&b.index))
+        });
+        if let Some(top_k) = request.top_k {
+            data.truncate(top_k);
+        }
+
+        let mut texts = request.documents.clone();
+        texts.push(request.query.clone());
+
+        Ok(RerankResponse {
+            object: "list".to_string(),
+            data,
+            model: request.model.as_str().to_string(),
+            usage: rerank::Usage {
+                total_tokens: EmbeddingClient::estimate_text_tokens(&texts),
+            },
+            served_by: None,
+            metadata: ResponseMetadata::default(),
+        })
+    }
+
+    /// Embeds a single text with the configured model.
+    pub async fn embed_text(&self, text: impl Into<String>) -> Result<Vec<f32>, VoyageError> {
+        let request = EmbeddingsRequest {
+            input: EmbeddingsInput::Single(text.into()),
+            model: self.embedding_model,
+            input_type: None,
+            truncation: None,
+            encoding_format: None,
+        };
+        let response = self.embeddings(request).await?;
+        Ok(response
+            .data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .unwrap_or_default())
+    }
+
+    /// Embeds a batch of texts with the configured model.
+    pub async fn embed_batch(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, VoyageError> {
+        let request = EmbeddingsRequest {
+            input: EmbeddingsInput::Multiple(texts),
+            model: self.embedding_model,
+            input_type: None,
+            truncation: None,
+            encoding_format: None,
+        };
+        let response = self.embeddings(request).await?;
+        Ok(response.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    fn record_call(&self) -> Result<(), VoyageError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let failure = self
+            .failures
+            .lock()
+            .expect("mock failure queue poisoned")
+            .pop_front();
+        match failure {
+            Some(failure) => {
+                debug!("MockVoyageClient returning scripted failure: {:?}", failure);
+                Err(failure.into())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for MockVoyageClient {
+    fn model_name(&self) -> &str {
+        self.embedding_model.as_str()
+    }
+
+    fn dimension(&self) -> usize {
+        self.embedding_model.embedding_dimension()
+    }
+
+    /// Embeds `texts` with the configured model. The mock's vectors do not depend on
+    /// `input_type`.
+    async fn embed_texts(
+        &self,
+        texts: &[String],
+        input_type: Option<InputType>,
+    ) -> Result<Vec<Vec<f32>>, VoyageError> {
+        let request = EmbeddingsRequest {
+            input: EmbeddingsInput::Multiple(texts.to_vec()),
+            model: self.embedding_model,
+            input_type,
+            truncation: None,
+            encoding_format: None,
+        };
+        let response = self.embeddings(request).await?;
+        Ok(response.data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+#[async_trait]
+impl EmbeddingsClient for MockVoyageClient {
+    fn embedding_model(&self) -> EmbeddingModel {
+        self.embedding_model
+    }
+
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        self.embeddings(request.clone()).await
+    }
+}
+
+#[async_trait]
+impl Reranker for MockVoyageClient {
+    /// Scores documents by [`relevance_score`](Self::relevance_score), counting the call
+    /// and returning any queued failure like the other mock methods.
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>, VoyageError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        let model = options.model.unwrap_or(self.rerank_model);
+        let request =
+            RerankRequest::new(query.to_string(), documents.to_vec(), model, options.top_k)?;
+        let response = MockVoyageClient::rerank(self, request).await?;
+        Ok(finish_rerank(response.data, documents, options))
+    }
+}
+
+#[async_trait]
+impl RerankClient for MockVoyageClient {
+    async fn rerank(&self, request: &RerankRequest) -> Result<RerankResponse, VoyageError> {
+        MockVoyageClient::rerank(self, request.clone()).await
+    }
+}
+
+/// Lowercased alphanumeric words of `text`.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
@@ -0,0 +1,536 @@
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use log::{debug, warn};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::client::compression::{gunzip, gzip, MAX_DECOMPRESSED_BYTES};
+use crate::client::embeddings_client::EmbeddingClient;
+use crate::client::mock_client::MockVoyageClient;
+use crate::config::VoyageConfig;
+use crate::models::embeddings::EmbeddingModel;
+use crate::models::rerank::{RerankModel, RerankRequest};
+
+/// Largest request the mock server accepts, headers included.
+const MAX_REQUEST_BYTES: usize = 64 * 1024 * 1024;
+
+/// Pause after a failed `accept` before trying again.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+type Assertion = Box<dyn Fn(&RecordedRequest) -> Result<(), String> + Send + Sync>;
+
+/// A request received by the [`MockServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names are lowercased.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Returns the first value of the header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses the body as JSON.
+    pub fn json(&self) -> Result<Value, serde_json::Error> {
+        serde_json::from_str(&self.body)
+    }
+}
+
+/// A scripted response for the [`MockServer`] to return instead of a generated one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    /// A response with the given status and a Voyage-style `{"detail": ...}` body.
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: json!({ "detail": format!("mock status {}", status) }).to_string(),
+        }
+    }
+
+    /// A response with the given status and JSON body.
+    pub fn json(status: u16, body: &Value) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /// A 200 response whose body is truncated, invalid JSON.
+    pub fn malformed() -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: r#"{"object": "list", "data": [{"embedding": [0.1,"#.to_string(),
+        }
+    }
+
+    /// Adds a `Retry-After` header, in seconds.
+    pub fn with_retry_after(self, seconds: u64) -> Self {
+        self.with_header("retry-after", seconds.to_string())
+    }
+
+    /// Adds an arbitrary response header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Replaces the response body.
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+#[derive(Default)]
+struct ServerState {
+    latency: Duration,
+    api_key: Option<String>,
+    scripted: VecDeque<MockResponse>,
+    assertions: Vec<Assertion>,
+    requests: Vec<RecordedRequest>,
+    failures: Vec<String>,
+    generator: MockVoyageClient,
+    /// Non-Voyage embedding models accepted, with their dimensions.
+    extra_models: Vec<(String, usize)>,
+}
+
+/// In-process HTTP server speaking the Voyage `/embeddings` and `/rerank` wire format,
+/// and optionally the OpenAI-compatible embeddings format (see [`MockServer::serve_model`]).
+///
+/// Unscripted requests get deterministic responses generated by [`MockVoyageClient`].
+/// Every response carries an `x-request-id` of `mock-req-<n>` unless it sets its own.
+/// Point a client at it with `VoyageConfig::with_base_url(server.base_url())`.
+/// The server stops when it is dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<ServerState>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server on an ephemeral localhost port.
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(ServerState::default()));
+        debug!("Starting MockServer on {}", address);
+
+        let task_state = state.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        // Errors such as running out of file descriptors persist for a
+                        // while; backing off keeps the loop from spinning on them.
+                        warn!("MockServer failed to accept a connection: {}", e);
+                        tokio::time::sleep(ACCEPT_BACKOFF).await;
+                        continue;
+                    }
+                };
+                let state = task_state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(stream, state).await {
+                        warn!("MockServer connection failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            address,
+            state,
+            task,
+        })
+    }
+
+    /// Returns the base URL to use as `VoyageConfig::base_url`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v1", self.address)
+    }
+
+    /// Returns a config for `api_key` that points at this server.
+    pub fn config(&self, api_key: impl Into<String>) -> VoyageConfig {
+        VoyageConfig::new(api_key.into()).with_base_url(self.base_url())
+    }
+
+    /// Delays every response by `latency`.
+    pub fn set_latency(&self, latency: Duration) -> &Self {
+        self.lock().latency = latency;
+        self
+    }
+
+    /// Answers 401 unless requests carry `Authorization: Bearer <api_key>`.
+    pub fn expect_api_key(&self, api_key: impl Into<String>) -> &Self {
+        self.lock().api_key = Some(api_key.into());
+        self
+    }
+
+    /// Also accepts embeddings requests for `model`, as an OpenAI-compatible server
+    /// would: vectors have `dimension` values, or the request's `dimensions`, and usage
+    /// reports `prompt_tokens`.
+    pub fn serve_model(&self, model: impl Into<String>, dimension: usize) -> &Self {
+        self.lock().extra_models.push((model.into(), dimension));
+        self
+    }
+
+    /// Queues a response for the next request, after any already queued.
+    pub fn enqueue(&self, response: MockResponse) -> &Self {
+        self.lock().scripted.push_back(response);
+        self
+    }
+
+    /// Runs `assertion` against every request; a failing request is answered with 400.
+    pub fn assert_each<F>(&self, assertion: F) -> &Self
+    where
+        F: Fn(&RecordedRequest) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.lock().assertions.push(Box::new(assertion));
+        self
+    }
+
+    /// Returns every request received so far.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Returns the messages of every failed assertion so far.
+    pub fn assertion_failures(&self) -> Vec<String> {
+        self.lock().failures.clone()
+    }
+
+    /// Panics if any assertion registered with `assert_each` failed.
+    pub fn verify(&self) {
+        let failures = self.assertion_failures();
+        assert!(
+            failures.is_empty(),
+            "MockServer assertions failed:\n{}",
+            failures.join("\n")
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ServerState> {
+        self.state.lock().expect("mock server state poisoned")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve_connection(mut stream: TcpStream, state: Arc<Mutex<ServerState>>) -> io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+    debug!("MockServer received {} {}", request.method, request.path);
+
+    let (latency, response) = {
+        let mut state = state.lock().expect("mock server state poisoned");
+        state.requests.push(request.clone());
+        let mut response = respond(&mut state, &request);
+        if !response
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("x-request-id"))
+        {
+            let id = format!("mock-req-{}", state.requests.len());
+            response = response.with_header("x-request-id", id);
+        }
+        (state.latency, response)
+    };
+
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+    let accepts_gzip = request
+        .header("accept-encoding")
+        .is_some_and(|encodings| encodings.split(',').any(|e| e.trim() == "gzip"));
+    write_response(&mut stream, &response, accepts_gzip).await
+}
+
+fn respond(state: &mut ServerState, request: &RecordedRequest) -> MockResponse {
+    let failures: Vec<String> = state
+        .assertions
+        .iter()
+        .filter_map(|assertion| assertion(request).err())
+        .collect();
+    if !failures.is_empty() {
+        let message = failures.join("; ");
+        state.failures.extend(failures);
+        return MockResponse::json(400, &json!({ "detail": message }));
+    }
+
+    if let Some(response) = state.scripted.pop_front() {
+        return response;
+    }
+
+    if let Some(api_key) = &state.api_key {
+        if request.header("authorization") != Some(format!("Bearer {}", api_key).as_str()) {
+            return MockResponse::json(401, &json!({ "detail": "Provided API key is invalid." }));
+        }
+    }
+
+    let body = match request.json() {
+        Ok(body) => body,
+        Err(e) => return MockResponse::json(400, &json!({ "detail": e.to_string() })),
+    };
+
+    let endpoint = request.path.strip_prefix("/v1").unwrap_or(&request.path);
+    let result = match (request.method.as_str(), endpoint) {
+        ("POST", "/embeddings") => embeddings_response(state, body),
+        ("POST", "/rerank") => rerank_response(body),
+        _ => return MockResponse::status(404),
+    };
+
+    match result {
+        Ok(body) => MockResponse::json(200, &body),
+        Err(message) => MockResponse::json(400, &json!({ "detail": message })),
+    }
+}
+
+fn embeddings_response(state: &ServerState, body: Value) -> Result<Value, String> {
+    let texts: Vec<String> = match body.get("input") {
+        Some(Value::String(text)) => vec![text.clone()],
+        Some(value @ Value::Array(_)) => {
+            serde_json::from_value(value.clone()).map_err(|e| format!("input: {}", e))?
+        }
+        _ => return Err("input must be a string or a list of strings".to_string()),
+    };
+    let model = body.get("model").cloned().unwrap_or_default();
+    let extra = state
+        .extra_models
+        .iter()
+        .find(|(name, _)| model.as_str() == Some(name.as_str()));
+    let dimension = match extra {
+        Some((_, dimension)) => body
+            .get("dimensions")
+            .and_then(Value::as_u64)
+            .map_or(*dimension, |d| d as usize),
+        None => serde_json::from_value::<EmbeddingModel>(model.clone())
+            .map_err(|e| format!("model: {}", e))?
+            .embedding_dimension(),
+    };
+
+    let data: Vec<Value> = texts
+        .iter()
+        .enumerate()
+        .map(|(index, text)| {
+            json!({
+                "object": "embedding",
+                "embedding": state.generator.embedding_with_dimension(text, dimension),
+                "index": index,
+            })
+        })
+        .collect();
+
+    let tokens = EmbeddingClient::estimate_text_tokens(&texts);
+    let usage = match extra {
+        Some(_) => json!({ "prompt_tokens": tokens, "total_tokens": tokens }),
+        None => json!({ "total_tokens": tokens }),
+    };
+    Ok(json!({
+        "object": "list",
+        "data": data,
+        "model": model,
+        "usage": usage,
+    }))
+}
+
+fn rerank_response(body: Value) -> Result<Value, String> {
+    let query = body
+        .get("query")
+        .and_then(Value::as_str)
+        .ok_or("query must be a string")?
+        .to_string();
+    let documents: Vec<String> =
+        serde_json::from_value(body.get("documents").cloned().unwrap_or_default())
+            .map_err(|e| format!("documents: {}", e))?;
+    let model: RerankModel = serde_json::from_value(body.get("model").cloned().unwrap_or_default())
+        .map_err(|e| format!("model: {}", e))?;
+    let top_k = body
+        .get("top_k")
+        .and_then(Value::as_u64)
+        .map(|k| k as usize);
+
+    let mut request =
+        RerankRequest::new(query, documents, model, top_k).map_err(|e| e.to_string())?;
+    if let Some(truncation) = body.get("truncation").and_then(Value::as_bool) {
+        request = request
+            .with_truncation(truncation)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut results: Vec<(usize, f64)> = request
+        .documents
+        .iter()
+        .enumerate()
+        .map(|(index, document)| {
+            (
+                index,
+                MockVoyageClient::relevance_score(&request.query, document),
+            )
+        })
+        .collect();
+    results.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    if let Some(top_k) = request.top_k {
+        results.truncate(top_k);
+    }
+
+    let mut texts = request.documents.clone();
+    texts.push(request.query.clone());
+
+    let data: Vec<Value> = results
+        .into_iter()
+        .map(|(index, score)| json!({ "relevance_score": score, "index": index }))
+        .collect();
+
+    Ok(json!({
+        "object": "list",
+        "data": data,
+        "model": body["model"],
+        "usage": { "total_tokens": EmbeddingClient::estimate_text_tokens(&texts) },
+    }))
+}
+
+async fn read_request(stream: &mut TcpStream) -> io::Result<Option<RecordedRequest>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0_u8; 8192];
+
+    let header_end = loop {
+        if let Some(position) = find_subsequence(&buffer, b"\r\n\r\n") {
+            break position;
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request too large",
+            ));
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default();
+    // Requests forwarded by a proxy carry the absolute form, `http://host/path`.
+    let path = match target.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        None => target,
+    }
+    .to_string();
+
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    if content_length > MAX_REQUEST_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "request too large",
+        ));
+    }
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+    let gzipped = headers
+        .iter()
+        .any(|(name, value)| name == "content-encoding" && value.eq_ignore_ascii_case("gzip"));
+    if gzipped {
+        body = gunzip(&body, MAX_DECOMPRESSED_BYTES)?;
+    }
+
+    Ok(Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    }))
+}
+
+/// Writes `response`, gzipping the body if the client accepts it.
+async fn write_response(
+    stream: &mut TcpStream,
+    response: &MockResponse,
+    gzip_body: bool,
+) -> io::Result<()> {
+    let body = if gzip_body {
+        gzip(response.body.as_bytes())?
+    } else {
+        response.body.as_bytes().to_vec()
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+        response.status,
+        reason_phrase(response.status),
+        body.len()
+    );
+    if gzip_body {
+        head.push_str("content-encoding: gzip\r\n");
+    }
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown")
+}
+
+fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
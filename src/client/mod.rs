@@ -1,12 +1,45 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod bm25;
+pub mod cache;
+pub mod cassette;
+pub mod circuit_breaker;
 pub mod client_limiter;
+pub mod compression;
+pub mod dedupe;
+pub mod embed_stream;
 pub mod embeddings_client;
+pub mod interceptor;
+pub mod isolation;
+pub mod key_pool;
+pub mod metrics;
+pub mod mock_client;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod rerank_client;
 pub mod retry;
 pub mod search_client;
+pub mod splitting;
+pub(crate) mod telemetry;
+pub mod transport;
 pub mod voyage_client;
 
 pub use crate::builder::search::SearchRequest;
 pub use crate::models::search::SearchResult;
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingClient;
+pub use bm25::Bm25Reranker;
+pub use cache::{CacheConfig, CacheStats, CachedEmbeddingsClient, EmbeddingCache};
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState, CircuitTransition};
 pub use client_limiter::RateLimiter;
+pub use compression::CompressionConfig;
+pub use embed_stream::{EmbedStreamOptions, StreamedEmbedding};
+pub use embeddings_client::EmbeddingsClient;
+pub use interceptor::{InterceptAction, InterceptedRequest, Interceptor};
+pub use isolation::{IsolatedBatch, ItemResult};
+pub use key_pool::{KeyPool, KeySelection, ServingKey};
+pub use metrics::{ClientMetrics, MetricsSnapshot};
+pub use mock_client::{MockFailure, MockVoyageClient};
 pub use rerank_client::RerankClient;
+pub use splitting::{Pooling, SplitConfig};
 pub use voyage_client::VoyageAiClient;
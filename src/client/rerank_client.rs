@@ -1,17 +1,17 @@
 use async_trait::async_trait;
-use log::{debug, info, warn};
-use reqwest::Client;
+use log::{debug, warn};
 use std::sync::Arc;
-use tokio::time::sleep;
 
+use crate::client::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::client::client_limiter::Endpoint;
+use crate::client::metrics::ClientMetrics;
+use crate::client::telemetry::OperationSpan;
+use crate::client::transport::{error_for_status, HttpTransport};
 use crate::client::RateLimiter;
 use crate::config::VoyageConfig;
 use crate::errors::VoyageError;
 use crate::models::rerank::{RerankRequest, RerankResponse};
 
-/// Base URL for the Voyage AI API.
-const BASE_URL: &str = "https://api.voyageai.com/v1";
-
 /// Client trait for interacting with the Voyage AI reranking API.
 #[async_trait]
 pub trait RerankClient: std::fmt::Debug + Send + Sync {
@@ -22,20 +22,34 @@ pub trait RerankClient: std::fmt::Debug + Send + Sync {
 /// Default implementation of RerankClient
 #[derive(Clone, Debug)]
 pub struct DefaultRerankClient {
-    client: Client,
+    transport: HttpTransport,
     config: VoyageConfig,
-    rate_limiter: Arc<RateLimiter>,
 }
 
 impl DefaultRerankClient {
     /// Creates a new `DefaultRerankClient` instance.
-    pub fn new(config: VoyageConfig, rate_limiter: Arc<RateLimiter>) -> Self {
+    pub fn new(config: VoyageConfig, rate_limiter: Arc<RateLimiter>) -> Result<Self, VoyageError> {
         debug!("Creating new DefaultRerankClient");
-        Self {
-            client: Client::new(),
+        Ok(Self {
+            transport: HttpTransport::new(config.clone(), rate_limiter)?,
             config,
-            rate_limiter,
-        }
+        })
+    }
+
+    pub(crate) fn config(&self) -> &VoyageConfig {
+        &self.config
+    }
+
+    /// Returns the traffic counters for this client.
+    pub fn metrics(&self) -> &ClientMetrics {
+        self.transport.metrics()
+    }
+
+    /// Returns the state of the endpoint's circuit breaker, if one is configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.transport
+            .circuit_breaker(Endpoint::Rerank)
+            .map(CircuitBreaker::state)
     }
 
     fn estimate_tokens(&self, request: &RerankRequest) -> u32 {
@@ -57,47 +71,49 @@ impl DefaultRerankClient {
 #[async_trait]
 impl RerankClient for DefaultRerankClient {
     async fn rerank(&self, request: &RerankRequest) -> Result<RerankResponse, VoyageError> {
-        let url = format!("{}/rerank", BASE_URL);
-        debug!("Reranking documents with URL: {}", url);
+        debug!(
+            "Reranking documents with URL: {}",
+            self.transport.url("/rerank")
+        );
 
         let estimated_tokens = self.estimate_tokens(request);
         debug!("Estimated tokens for request: {}", estimated_tokens);
 
-        let wait_time = self
-            .rate_limiter
-            .check_reranking_limit(estimated_tokens)
+        let span = OperationSpan::rerank(
+            request.model.as_str(),
+            request.documents.len(),
+            estimated_tokens,
+        );
+        let result = span
+            .instrument(self.execute_rerank_request(request, estimated_tokens, &span))
             .await;
+        span.finish(&result);
+        result
+    }
+}
 
-        if wait_time.as_secs() > 0 {
-            info!(
-                "Rate limit reached. Waiting for {} seconds",
-                wait_time.as_secs()
-            );
-            sleep(wait_time).await;
-        }
-
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(self.config.api_key())
-            .json(request)
-            .send()
+impl DefaultRerankClient {
+    async fn execute_rerank_request(
+        &self,
+        request: &RerankRequest,
+        estimated_tokens: u32,
+        span: &OperationSpan,
+    ) -> Result<RerankResponse, VoyageError> {
+        let (response, key, metadata) = self
+            .transport
+            .call(Endpoint::Rerank, request, estimated_tokens)
             .await?;
+        span.record_status(response.status.as_u16());
+        let text = &response.body;
 
-        let status = response.status();
-        let text = response.text().await?;
-
-        
-
-        match status {
+        match response.status {
             reqwest::StatusCode::OK => {
                 debug!("Rerank request successful");
-                debug!("Raw API response: {}", text);
-                let rerank_response: RerankResponse = serde_json::from_str(&text).map_err(|e| {
-                    warn!("Failed to parse rerank response: {:?}", e);
-                    warn!("Raw response: {}", text);
-                    VoyageError::JsonError(e.to_string())
-                })?;
+                let mut rerank_response: RerankResponse =
+                    serde_json::from_str(text).map_err(|e| {
+                        warn!("Failed to parse rerank response: {:?}", e);
+                        VoyageError::JsonError(e.to_string())
+                    })?;
 
                 if rerank_response.data.is_empty() {
                     warn!("Rerank response contains no results");
@@ -108,20 +124,18 @@ impl RerankClient for DefaultRerankClient {
                     );
                 }
 
-                self.rate_limiter
-                    .update_reranking_usage(rerank_response.usage.total_tokens)
+                span.record_tokens(rerank_response.usage.total_tokens);
+                key.limiter()
+                    .update_usage(Endpoint::Rerank, rerank_response.usage.total_tokens)
                     .await;
+                rerank_response.served_by = Some(key.label().to_string());
+                rerank_response.metadata = metadata;
 
                 Ok(rerank_response)
             }
-            reqwest::StatusCode::UNAUTHORIZED => {
-                warn!("Unauthorized request: {}", text);
-                Err(VoyageError::Unauthorized)
-            }
-            _ => {
+            status => {
                 warn!("Rerank request failed with status: {}", status);
-                warn!("Error response body: {}", text);
-                Err(VoyageError::ApiError(status, text))
+                Err(error_for_status(&response))
             }
         }
     }
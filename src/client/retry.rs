@@ -3,6 +3,41 @@ use log::{debug, info, warn};
 use std::time::Duration;
 use tokio::time::sleep;
 
+/// Retry behaviour for API requests, applied by the shared transport.
+///
+/// Rate-limited (429) and transient server responses (500, 502, 503, 504), timeouts and
+/// connection failures are retried. A 429 waits for its `Retry-After` header when present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Retries after the first attempt; `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every retry after it.
+    pub initial_delay: Duration,
+    /// Upper bound on any single delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Returns the backoff delay before retry number `retry` (starting at 0).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry);
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
 /// Retries an asynchronous operation with exponential backoff.
 ///
 /// This function will retry the given operation up to `max_retries` times,
@@ -2,16 +2,22 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
 use crate::builder::search::SearchRequest;
-use crate::client::{embeddings_client::EmbeddingClient, rerank_client::RerankClient};
+use crate::client::telemetry::OperationSpan;
+use crate::client::{embeddings_client::EmbeddingsClient, rerank_client::RerankClient};
 use crate::errors::VoyageError;
 use crate::models::search::{SearchResult, SearchType};
+use crate::traits::llm::Embedder;
 
 // Define SearchRequest struct here
 
 /// Client for performing search operations.
+///
+/// Queries are embedded with [`Embedder::embed_query`] and documents with
+/// [`Embedder::embed_documents`], so any [`Embedder`] can back the search, including any
+/// `Arc<dyn EmbeddingsClient>`.
 #[derive(Debug, Clone)]
-pub struct SearchClient {
-    embedding_client: EmbeddingClient,
+pub struct SearchClient<E = Arc<dyn EmbeddingsClient>> {
+    embedder: E,
     #[allow(dead_code)]
     rerank_client: Arc<Box<dyn RerankClient>>,
     #[allow(dead_code)]
@@ -22,22 +28,33 @@ pub struct SearchClient {
     avg_doc_length: Arc<Mutex<f32>>,
 }
 
-impl SearchClient {
+impl<E: Embedder> SearchClient<E> {
 
     pub async fn search(&self, request: &SearchRequest) -> Result<Vec<SearchResult>, VoyageError> {
-        match request.search_type {
-            SearchType::Similarity => self.nearest_neighbor_search(request).await,
-            SearchType::NearestNeighbor => self.nearest_neighbor_search(request).await,
-            SearchType::BM25 => self.bm25_search(request).await,
-            _ => Err(VoyageError::SearchBuilderError(
-                "Unsupported search type".to_string(),
-            )),
+        let document_count = request.documents.as_ref().map_or(0, Vec::len);
+        let span = OperationSpan::search(&format!("{:?}", request.search_type), document_count);
+        let result = span
+            .instrument(async {
+                match request.search_type {
+                    SearchType::Similarity => self.nearest_neighbor_search(request).await,
+                    SearchType::NearestNeighbor => self.nearest_neighbor_search(request).await,
+                    SearchType::BM25 => self.bm25_search(request).await,
+                    _ => Err(VoyageError::SearchBuilderError(
+                        "Unsupported search type".to_string(),
+                    )),
+                }
+            })
+            .await;
+        if let Ok(results) = &result {
+            span.record_results(results.len());
         }
+        span.finish(&result);
+        result
     }
 
-    pub fn new(embedding_client: EmbeddingClient, rerank_client: impl RerankClient + 'static) -> Self {
+    pub fn new(embedder: E, rerank_client: impl RerankClient + 'static) -> Self {
         Self {
-            embedding_client,
+            embedder,
             rerank_client: Arc::new(Box::new(rerank_client)),
             document_index: Arc::new(Mutex::new(HashMap::new())),
             idf_scores: Arc::new(Mutex::new(HashMap::new())),
@@ -45,6 +62,11 @@ impl SearchClient {
         }
     }
 
+    /// The embedder used for queries and documents.
+    pub fn embedder(&self) -> &E {
+        &self.embedder
+    }
+
     // ... (keep existing methods)
     #[allow(dead_code)]
     async fn nearest_neighbor_search(
@@ -52,9 +74,9 @@ impl SearchClient {
         request: &SearchRequest,
     ) -> Result<Vec<SearchResult>, VoyageError> {
         // Obtain embeddings for the query and documents
-        let query_embedding = self.embedding_client.embed(&request.query.query).await?;
+        let query_embedding = self.embedder.embed_query(&request.query.query).await?;
         let document_embeddings = match &request.documents {
-            Some(docs) => self.embedding_client.embed_batch(docs).await?,
+            Some(docs) => self.embedder.embed_documents(docs).await?,
             None => {
                 return Err(VoyageError::MissingDocuments(
                     "Missing documents".to_string(),
@@ -82,7 +104,7 @@ impl SearchClient {
             .collect::<Vec<_>>();
 
         // Sort results by distance (ascending)
-        results.sort_by(|a, b| a.score.cmp(&b.score));
+        results.sort_by_key(|r| r.score);
 
         // Truncate to top_k if specified
         if let Some(top_k) = request.top_k {
@@ -109,10 +131,10 @@ impl SearchClient {
         request: &SearchRequest,
     ) -> Result<Vec<SearchResult>, VoyageError> {
         // Obtain embeddings for the query and documents
-        let query_embedding = self.embedding_client.embed(&request.query.query).await?;
+        let query_embedding = self.embedder.embed_query(&request.query.query).await?;
         let document_embeddings = self
-            .embedding_client
-            .embed_batch(request.documents.as_ref().unwrap())
+            .embedder
+            .embed_documents(request.documents.as_ref().unwrap())
             .await?;
 
         // Calculate cosine similarities
@@ -133,7 +155,7 @@ impl SearchClient {
             .collect::<Vec<_>>();
 
         // Sort results by similarity (descending)
-        results.sort_by(|a, b| b.score.cmp(&a.score));
+        results.sort_by_key(|r| std::cmp::Reverse(r.score));
 
         // Truncate to top_k if specified
         if let Some(top_k) = request.top_k {
@@ -188,7 +210,7 @@ impl SearchClient {
             .collect::<Vec<_>>();
 
         // Sort results by score (descending)
-        results.sort_by(|a, b| b.score.cmp(&a.score));
+        results.sort_by_key(|r| std::cmp::Reverse(r.score));
 
         // Truncate to top_k if specified
         if let Some(top_k) = request.top_k {
@@ -0,0 +1,280 @@
+//! Splitting of over-length embedding inputs.
+//!
+//! With [`VoyageConfig::split_long_inputs`](crate::config::VoyageConfig) set, an input
+//! longer than the window is cut into overlapping windows, every window is embedded, and
+//! the window embeddings are pooled back into one vector per input.
+
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::client::embed_stream::MAX_BATCH_SIZE;
+use crate::client::embeddings_client::EmbeddingClient;
+use crate::errors::VoyageError;
+use crate::models::embeddings::{
+    DedupeStats, EmbeddingData, EmbeddingModel, EmbeddingsInput, EmbeddingsRequest,
+    EmbeddingsResponse, SplitInput, Usage,
+};
+use crate::models::usage::estimate_tokens;
+
+/// Bytes per token assumed by [`estimate_tokens`].
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Overlap between consecutive windows unless configured otherwise.
+pub const DEFAULT_OVERLAP_TOKENS: usize = 200;
+
+/// How the embeddings of an input's windows are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pooling {
+    /// Element-wise mean, scaled back to unit length.
+    #[default]
+    Mean,
+    /// Element-wise maximum, scaled to unit length.
+    Max,
+    /// The first window's embedding; later windows are still embedded and billed.
+    First,
+}
+
+/// Window size, overlap and pooling for splitting long inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitConfig {
+    /// Estimated tokens per window. `None` uses the model's context length; larger
+    /// values are capped at it.
+    pub window_tokens: Option<usize>,
+    /// Estimated tokens shared by consecutive windows.
+    pub overlap_tokens: usize,
+    pub pooling: Pooling,
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            window_tokens: None,
+            overlap_tokens: DEFAULT_OVERLAP_TOKENS,
+            pooling: Pooling::default(),
+        }
+    }
+}
+
+impl SplitConfig {
+    pub fn with_window_tokens(mut self, window_tokens: usize) -> Self {
+        self.window_tokens = Some(window_tokens);
+        self
+    }
+
+    pub fn with_overlap_tokens(mut self, overlap_tokens: usize) -> Self {
+        self.overlap_tokens = overlap_tokens;
+        self
+    }
+
+    pub fn with_pooling(mut self, pooling: Pooling) -> Self {
+        self.pooling = pooling;
+        self
+    }
+
+    /// The window size used for `model`.
+    pub fn window_for(&self, model: EmbeddingModel) -> usize {
+        let context = model.max_context_length();
+        self.window_tokens.unwrap_or(context).clamp(1, context)
+    }
+}
+
+/// Cuts `text` into windows of about `window_tokens` tokens, each starting
+/// `overlap_tokens` before the previous one ended. Windows end on whitespace when there
+/// is some in their last quarter. Text that fits one window is returned whole.
+pub fn split_text(text: &str, window_tokens: usize, overlap_tokens: usize) -> Vec<String> {
+    let window = window_tokens.max(1) * BYTES_PER_TOKEN;
+    let overlap = overlap_tokens.min(window_tokens.saturating_sub(1)) * BYTES_PER_TOKEN;
+    if text.len() <= window {
+        return vec![text.to_string()];
+    }
+
+    let mut windows = Vec::new();
+    let mut start = 0;
+    loop {
+        let mut end = floor_boundary(text, (start + window).min(text.len()));
+        if end < text.len() {
+            let tail = start + window * 3 / 4;
+            if let Some(space) = text[start..end].rfind(char::is_whitespace) {
+                if start + space > tail {
+                    end = start + space;
+                }
+            }
+        }
+        if end <= start {
+            // A window narrower than one character; take the whole character.
+            end = ceil_boundary(text, start + 1);
+        }
+        windows.push(text[start..end].to_string());
+        if end >= text.len() {
+            return windows;
+        }
+        start = ceil_boundary(text, end.saturating_sub(overlap).max(start + 1));
+    }
+}
+
+/// Combines window embeddings into one vector.
+pub fn pool(embeddings: &[Vec<f32>], pooling: Pooling) -> Vec<f32> {
+    let Some(first) = embeddings.first() else {
+        return Vec::new();
+    };
+    if embeddings.len() == 1 || pooling == Pooling::First {
+        return first.clone();
+    }
+    let mut pooled = first.clone();
+    for embedding in &embeddings[1..] {
+        for (value, other) in pooled.iter_mut().zip(embedding) {
+            *value = match pooling {
+                Pooling::Max => value.max(*other),
+                _ => *value + other,
+            };
+        }
+    }
+    let norm = pooled.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        pooled.iter_mut().for_each(|v| *v /= norm);
+    }
+    pooled
+}
+
+fn floor_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_boundary(text: &str, mut index: usize) -> usize {
+    while index < text.len() && !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+impl EmbeddingClient {
+    /// Embeds `request` with every over-length input split and pooled, or returns
+    /// `None` if no input needs splitting.
+    pub(crate) async fn create_split_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+        split: &SplitConfig,
+    ) -> Option<Result<EmbeddingsResponse, VoyageError>> {
+        let texts = match &request.input {
+            EmbeddingsInput::Single(text) => std::slice::from_ref(text),
+            EmbeddingsInput::Multiple(texts) => texts.as_slice(),
+        };
+        let window_tokens = split.window_for(request.model);
+        if texts
+            .iter()
+            .all(|text| estimate_tokens(text) <= window_tokens)
+        {
+            return None;
+        }
+
+        let mut windows = Vec::new();
+        let mut owners = Vec::new();
+        let mut split_inputs = Vec::new();
+        for (index, text) in texts.iter().enumerate() {
+            let parts = split_text(text, window_tokens, split.overlap_tokens);
+            if parts.len() > 1 {
+                split_inputs.push(SplitInput {
+                    index,
+                    windows: parts.len(),
+                });
+            }
+            owners.extend(std::iter::repeat_n(index, parts.len()));
+            windows.extend(parts);
+        }
+        debug!(
+            "Split {} of {} inputs into {} windows",
+            split_inputs.len(),
+            texts.len(),
+            windows.len()
+        );
+
+        Some(
+            self.embed_windows(request, texts.len(), windows, owners, split, split_inputs)
+                .await,
+        )
+    }
+
+    async fn embed_windows(
+        &self,
+        request: &EmbeddingsRequest,
+        input_count: usize,
+        windows: Vec<String>,
+        owners: Vec<usize>,
+        split: &SplitConfig,
+        split_inputs: Vec<SplitInput>,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        let token_limit = request.model.max_tokens_per_request();
+        let mut per_input: Vec<Vec<Vec<f32>>> = vec![Vec::new(); input_count];
+        let mut total_tokens = 0;
+        let mut dedupe: Option<DedupeStats> = None;
+        let mut last = None;
+
+        let mut start = 0;
+        while start < windows.len() {
+            // Fill each request up to the input count and token limits.
+            let mut end = start;
+            let mut tokens = 0;
+            while end < windows.len() && end - start < MAX_BATCH_SIZE {
+                let next = estimate_tokens(&windows[end]);
+                if end > start && tokens + next > token_limit {
+                    break;
+                }
+                tokens += next;
+                end += 1;
+            }
+
+            let part = EmbeddingsRequest {
+                input: EmbeddingsInput::Multiple(windows[start..end].to_vec()),
+                model: request.model,
+                input_type: request.input_type,
+                truncation: request.truncation,
+                encoding_format: request.encoding_format,
+            };
+            let mut response = self.create_unsplit_embedding(&part).await?;
+            if response.data.len() != end - start {
+                return Err(VoyageError::JsonError(format!(
+                    "Expected {} embeddings, got {}",
+                    end - start,
+                    response.data.len()
+                )));
+            }
+            response.data.sort_by_key(|d| d.index);
+            for (owner, data) in owners[start..end].iter().zip(response.data.drain(..)) {
+                per_input[*owner].push(data.embedding);
+            }
+            total_tokens += response.usage.total_tokens;
+            if let Some(stats) = response.dedupe.take() {
+                let sum = dedupe.get_or_insert_with(DedupeStats::default);
+                sum.inputs_saved += stats.inputs_saved;
+                sum.tokens_saved += stats.tokens_saved;
+            }
+            last = Some(response);
+            start = end;
+        }
+
+        let Some(last) = last else {
+            return Err(VoyageError::BuilderError(
+                "no inputs to embed after splitting".to_string(),
+            ));
+        };
+        Ok(EmbeddingsResponse {
+            data: per_input
+                .iter()
+                .enumerate()
+                .map(|(index, embeddings)| EmbeddingData {
+                    object: "embedding".to_string(),
+                    embedding: pool(embeddings, split.pooling),
+                    index,
+                })
+                .collect(),
+            usage: Usage { total_tokens },
+            dedupe,
+            split: split_inputs,
+            ..last
+        })
+    }
+}
@@ -0,0 +1,195 @@
+//! Tracing instrumentation for API operations.
+//!
+//! With the `tracing` feature enabled, every operation gets a span carrying its model,
+//! input count, token estimate and outcome. Without it, these types compile to no-ops.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use log::info;
+use tokio::time::sleep;
+
+use crate::errors::VoyageError;
+
+#[cfg(feature = "tracing")]
+type SpanHandle = tracing::Span;
+#[cfg(not(feature = "tracing"))]
+type SpanHandle = ();
+
+/// Span around one API operation (embeddings, rerank or search).
+#[derive(Debug)]
+pub(crate) struct OperationSpan {
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    span: SpanHandle,
+    started: Instant,
+}
+
+impl OperationSpan {
+    /// Opens a `voyage.embeddings` span.
+    pub(crate) fn embeddings(model: &str, input_count: usize, estimated_tokens: u32) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            Self::from_span(tracing::info_span!(
+                "voyage.embeddings",
+                model,
+                input_count,
+                estimated_tokens,
+                actual_tokens = tracing::field::Empty,
+                status = tracing::field::Empty,
+                retries = tracing::field::Empty,
+                api_key = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                error = tracing::field::Empty,
+            ))
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (model, input_count, estimated_tokens);
+            Self::from_span(())
+        }
+    }
+
+    /// Opens a `voyage.rerank` span.
+    pub(crate) fn rerank(model: &str, input_count: usize, estimated_tokens: u32) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            Self::from_span(tracing::info_span!(
+                "voyage.rerank",
+                model,
+                input_count,
+                estimated_tokens,
+                actual_tokens = tracing::field::Empty,
+                status = tracing::field::Empty,
+                retries = tracing::field::Empty,
+                api_key = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                error = tracing::field::Empty,
+            ))
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (model, input_count, estimated_tokens);
+            Self::from_span(())
+        }
+    }
+
+    /// Opens a `voyage.search` span.
+    pub(crate) fn search(search_type: &str, input_count: usize) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            Self::from_span(tracing::info_span!(
+                "voyage.search",
+                search_type,
+                input_count,
+                result_count = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                error = tracing::field::Empty,
+            ))
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (search_type, input_count);
+            Self::from_span(())
+        }
+    }
+
+    fn from_span(span: SpanHandle) -> Self {
+        Self {
+            span,
+            started: Instant::now(),
+        }
+    }
+
+    /// Runs `future` inside this span.
+    pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Instrument::instrument(future, self.span.clone())
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            future
+        }
+    }
+
+    /// Records the HTTP status of the response.
+    pub(crate) fn record_status(&self, status: u16) {
+        #[cfg(feature = "tracing")]
+        self.span.record("status", status);
+        #[cfg(not(feature = "tracing"))]
+        let _ = status;
+    }
+
+    /// Records the token count reported by the API.
+    pub(crate) fn record_tokens(&self, tokens: u32) {
+        #[cfg(feature = "tracing")]
+        self.span.record("actual_tokens", tokens);
+        #[cfg(not(feature = "tracing"))]
+        let _ = tokens;
+    }
+
+    /// Records the number of results returned.
+    pub(crate) fn record_results(&self, count: usize) {
+        #[cfg(feature = "tracing")]
+        self.span.record("result_count", count);
+        #[cfg(not(feature = "tracing"))]
+        let _ = count;
+    }
+
+    /// Records latency and the outcome, and emits a completion event.
+    pub(crate) fn finish<T>(&self, result: &Result<T, VoyageError>) {
+        let latency = self.started.elapsed();
+        #[cfg(feature = "tracing")]
+        {
+            let latency_ms = latency.as_millis() as u64;
+            self.span.record("latency_ms", latency_ms);
+            match result {
+                Ok(_) => tracing::debug!(parent: &self.span, latency_ms, "completed"),
+                Err(e) => {
+                    self.span.record("error", tracing::field::display(e));
+                    tracing::warn!(parent: &self.span, latency_ms, error = %e, "failed");
+                }
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (latency, result);
+    }
+}
+
+/// Records the retry count on the operation span the caller is running in.
+pub(crate) fn record_retries(retries: u32) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("retries", retries);
+    #[cfg(not(feature = "tracing"))]
+    let _ = retries;
+}
+
+/// Records the redacted label of the serving API key on the caller's operation span.
+pub(crate) fn record_api_key(label: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("api_key", label);
+    #[cfg(not(feature = "tracing"))]
+    let _ = label;
+}
+
+/// Sleeps for a rate-limit wait inside a `voyage.rate_limit_wait` span.
+pub(crate) async fn wait_for_rate_limit(endpoint: &str, wait_time: Duration) {
+    info!(
+        "Rate limit reached. Waiting for {} seconds",
+        wait_time.as_secs()
+    );
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::info_span!(
+            "voyage.rate_limit_wait",
+            endpoint,
+            wait_ms = wait_time.as_millis() as u64
+        );
+        tracing::Instrument::instrument(sleep(wait_time), span).await;
+    }
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = endpoint;
+        sleep(wait_time).await;
+    }
+}
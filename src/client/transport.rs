@@ -0,0 +1,507 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE,
+    RETRY_AFTER,
+};
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use tokio::time::sleep;
+
+use crate::client::cassette::{Cassette, CassetteMode};
+use crate::client::circuit_breaker::{CircuitBreaker, EndpointCircuits};
+use crate::client::client_limiter::{Endpoint, RateLimiter};
+use crate::client::compression::{gunzip, gzip, MAX_DECOMPRESSED_BYTES};
+use crate::client::interceptor::{InterceptAction, InterceptedRequest};
+use crate::client::key_pool::{KeyPool, KeySelection, ServingKey};
+use crate::client::metrics::ClientMetrics;
+use crate::client::telemetry::{record_api_key, record_retries, wait_for_rate_limit};
+use crate::config::{ApiKey, VoyageConfig};
+use crate::errors::VoyageError;
+use crate::models::metadata::ResponseMetadata;
+
+/// Base URL for the Voyage AI API, used when the config does not set one.
+pub const DEFAULT_BASE_URL: &str = "https://api.voyageai.com/v1";
+
+/// Wait assumed for a 429 response that carries no usable `Retry-After` header.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// A response as it came off the wire, before it is decoded.
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// Shared HTTP plumbing for the endpoint clients.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    client: Client,
+    config: VoyageConfig,
+    keys: KeyPool,
+    circuits: Option<Arc<EndpointCircuits>>,
+}
+
+impl HttpTransport {
+    /// Creates a new `HttpTransport` for the given config.
+    ///
+    /// Requests are rate limited by `rate_limiter`, unless the config carries a key pool,
+    /// whose keys have limiters of their own.
+    ///
+    /// Fails with `VoyageError::ConfigError` if the HTTP client cannot be built with the
+    /// configured proxy, certificates or client identity.
+    pub fn new(config: VoyageConfig, rate_limiter: Arc<RateLimiter>) -> Result<Self, VoyageError> {
+        let mut builder = Client::builder();
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        let client = config.network.configure(builder)?.build().map_err(|e| {
+            VoyageError::ConfigError(format!("cannot build HTTP client: {}", error_chain(&e)))
+        })?;
+        let keys = match &config.key_pool {
+            Some(pool) => {
+                pool.init_limits(config.rate_limits);
+                pool.clone()
+            }
+            None => KeyPool::single(config.api_key.clone(), rate_limiter),
+        };
+        let circuits = config
+            .circuit_breaker
+            .map(|breaker| Arc::new(EndpointCircuits::new(breaker, &config.circuit_listeners)));
+        Ok(Self {
+            client,
+            config,
+            keys,
+            circuits,
+        })
+    }
+
+    /// Returns the circuit breaker for `endpoint`, if the config enables one.
+    pub fn circuit_breaker(&self, endpoint: Endpoint) -> Option<&CircuitBreaker> {
+        self.circuits
+            .as_ref()
+            .map(|circuits| circuits.get(endpoint))
+    }
+
+    /// Returns the traffic counters this transport reports into.
+    pub fn metrics(&self) -> &ClientMetrics {
+        &self.config.metrics
+    }
+
+    /// Returns the keys requests are sent with.
+    pub fn keys(&self) -> &KeyPool {
+        &self.keys
+    }
+
+    /// Sends `body` to `endpoint` with a key from the pool and returns the response along
+    /// with the key that served it and the call's [`ResponseMetadata`].
+    ///
+    /// Waits for the chosen key's rate limiter first. A 401 quarantines the key and a 429
+    /// under [`KeySelection::Failover`] moves on; either way the request is retried with
+    /// the next key while one is left. Callers record usage on the returned key's limiter.
+    ///
+    /// With a circuit breaker configured, fails fast with `VoyageError::CircuitOpen` while
+    /// the endpoint's circuit is open.
+    pub async fn call<T: Serialize + ?Sized>(
+        &self,
+        endpoint: Endpoint,
+        body: &T,
+        estimated_tokens: u32,
+    ) -> Result<(RawResponse, ServingKey, ResponseMetadata), VoyageError> {
+        let started = Instant::now();
+        let mut metadata = ResponseMetadata::default();
+        let result = match self.circuit_breaker(endpoint) {
+            None => {
+                self.call_with_keys(endpoint, body, estimated_tokens, &mut metadata)
+                    .await
+            }
+            Some(breaker) => {
+                let permit = breaker.acquire()?;
+                let result = self
+                    .call_with_keys(endpoint, body, estimated_tokens, &mut metadata)
+                    .await;
+                permit.record(!is_outage(&result));
+                result
+            }
+        };
+        let (response, key) = result?;
+        metadata.latency = started.elapsed();
+        metadata.read_headers(&response.headers);
+        Ok((response, key, metadata))
+    }
+
+    async fn call_with_keys<T: Serialize + ?Sized>(
+        &self,
+        endpoint: Endpoint,
+        body: &T,
+        estimated_tokens: u32,
+        metadata: &mut ResponseMetadata,
+    ) -> Result<(RawResponse, ServingKey), VoyageError> {
+        let mut tried = Vec::new();
+        let mut last = None;
+        while let Some(key) = self.keys.select(endpoint, &tried).await? {
+            let wait_time = key.limiter().check_limit(endpoint, estimated_tokens).await;
+            if wait_time.as_secs() > 0 {
+                wait_for_rate_limit(endpoint.name(), wait_time).await;
+                metadata.rate_limit_wait += wait_time;
+            }
+
+            record_api_key(key.label());
+            debug!(
+                "Sending {} request with API key {}",
+                endpoint.name(),
+                key.label()
+            );
+            // A 429 is retried on the same key only when there is no other key to move to.
+            let retry_rate_limited = !self.keys.can_fail_over(&key, &tried);
+            let response = self
+                .post_json_as(
+                    endpoint.path(),
+                    body,
+                    key.api_key(),
+                    retry_rate_limited,
+                    &mut metadata.retries,
+                )
+                .await?;
+            let fail_over = match response.status {
+                StatusCode::UNAUTHORIZED => {
+                    self.keys.quarantine(&key);
+                    true
+                }
+                StatusCode::TOO_MANY_REQUESTS => {
+                    self.keys.rate_limited(&key);
+                    self.keys.selection() == KeySelection::Failover
+                }
+                _ => false,
+            };
+            if !fail_over || self.keys.len() < 2 {
+                return Ok((response, key));
+            }
+            tried.push(key.index());
+            last = Some((response, key));
+        }
+        last.ok_or(VoyageError::Unauthorized)
+    }
+
+    /// Returns the full URL for an endpoint path such as `/embeddings`.
+    pub fn url(&self, path: &str) -> String {
+        let base_url = if self.config.base_url.is_empty() {
+            DEFAULT_BASE_URL
+        } else {
+            self.config.base_url.trim_end_matches('/')
+        };
+        format!("{}{}", base_url, path)
+    }
+
+    /// Posts `body` as JSON to `path` with the pool's first key and returns the raw
+    /// response. Unlike [`HttpTransport::call`], this neither rate limits nor fails over.
+    pub async fn post_json<T: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<RawResponse, VoyageError> {
+        let key = self.keys.primary().clone();
+        self.post_json_as(path, body, &key, true, &mut 0).await
+    }
+
+    /// Posts `body` as JSON to `path` with `key` and returns the raw response, adding the
+    /// number of retries it took to `retries`. A 429 is returned without retrying unless
+    /// `retry_rate_limited` is set.
+    ///
+    /// The request runs through the config's interceptors first. If the config carries a
+    /// cassette, the call is then recorded or replayed through it.
+    async fn post_json_as<T: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &T,
+        key: &ApiKey,
+        retry_rate_limited: bool,
+        retries: &mut u32,
+    ) -> Result<RawResponse, VoyageError> {
+        let mut request = InterceptedRequest::new("POST", path, serde_json::to_value(body)?);
+        let result = self
+            .intercept(&mut request, key, retry_rate_limited, retries)
+            .await;
+
+        let interceptors = &self.config.interceptors;
+        match &result {
+            Err(error) => {
+                for interceptor in interceptors {
+                    interceptor.on_error(&request, error).await;
+                }
+            }
+            Ok(response) if !response.status.is_success() && !interceptors.is_empty() => {
+                let error = error_for_status(response);
+                for interceptor in interceptors {
+                    interceptor.on_error(&request, &error).await;
+                }
+            }
+            Ok(_) => {}
+        }
+        result
+    }
+
+    async fn intercept(
+        &self,
+        request: &mut InterceptedRequest,
+        key: &ApiKey,
+        retry_rate_limited: bool,
+        retries: &mut u32,
+    ) -> Result<RawResponse, VoyageError> {
+        let mut short_circuit = None;
+        for interceptor in &self.config.interceptors {
+            if let InterceptAction::Respond(response) = interceptor.before_request(request).await? {
+                debug!(
+                    "Interceptor {:?} answered {} itself",
+                    interceptor, request.path
+                );
+                short_circuit = Some(response);
+                break;
+            }
+        }
+
+        let mut response = match short_circuit {
+            Some(response) => response,
+            None => {
+                self.dispatch_with_retry(request, key, retry_rate_limited, retries)
+                    .await?
+            }
+        };
+        for interceptor in &self.config.interceptors {
+            interceptor.after_response(request, &mut response).await?;
+        }
+        Ok(response)
+    }
+
+    async fn dispatch_with_retry(
+        &self,
+        request: &InterceptedRequest,
+        key: &ApiKey,
+        retry_rate_limited: bool,
+        total_retries: &mut u32,
+    ) -> Result<RawResponse, VoyageError> {
+        let retry = self.config.retry;
+        let mut retries = 0;
+        loop {
+            let result = self
+                .dispatch(&request.path, &request.body, &request.headers, key)
+                .await;
+            if retries >= retry.max_retries {
+                return result;
+            }
+            let rate_limited = matches!(&result, Ok(response) if response.status == 429);
+            if rate_limited && !retry_rate_limited {
+                return result;
+            }
+            let Some(delay) = retry_delay(&result, retry.backoff(retries)) else {
+                return result;
+            };
+
+            retries += 1;
+            *total_retries += 1;
+            record_retries(retries);
+            match &result {
+                Ok(response) => warn!(
+                    "{} returned {}; retry {}/{} in {:?}",
+                    request.path, response.status, retries, retry.max_retries, delay
+                ),
+                Err(e) => warn!(
+                    "{} failed: {}; retry {}/{} in {:?}",
+                    request.path, e, retries, retry.max_retries, delay
+                ),
+            }
+            sleep(delay).await;
+        }
+    }
+
+    async fn dispatch(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+        headers: &HeaderMap,
+        api_key: &ApiKey,
+    ) -> Result<RawResponse, VoyageError> {
+        let Some(cassette) = &self.config.cassette else {
+            return self.send(path, body, headers, api_key).await;
+        };
+        let key = Cassette::request_key("POST", path, body, api_key.expose());
+        match cassette.mode() {
+            CassetteMode::ReplayOnly => {
+                debug!("Replaying POST {} from cassette", path);
+                cassette.replay(&key)
+            }
+            CassetteMode::Record => {
+                let response = self.send(path, body, headers, api_key).await?;
+                cassette.record(key, &response, api_key.expose())?;
+                Ok(response)
+            }
+            CassetteMode::Passthrough => self.send(path, body, headers, api_key).await,
+        }
+    }
+
+    async fn send(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+        headers: &HeaderMap,
+        api_key: &ApiKey,
+    ) -> Result<RawResponse, VoyageError> {
+        let url = self.url(path);
+        debug!("Sending POST request to {}", url);
+        let json = serde_json::to_vec(body)?;
+        if let Some(body) = self
+            .config
+            .body_log_policy
+            .render(&String::from_utf8_lossy(&json))
+        {
+            debug!("Request body: {}", body);
+        }
+
+        let compression = self.config.compression;
+        let mut request = self
+            .client
+            .post(&url)
+            .bearer_auth(api_key.expose())
+            .headers(headers.clone())
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        if compression.accept_gzip {
+            request = request.header(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+        let uncompressed_len = json.len();
+        let gzip_request = compression.should_compress(uncompressed_len);
+        let payload = if gzip_request {
+            let compressed = gzip(&json)?;
+            debug!(
+                "Compressed request body from {} to {} bytes",
+                uncompressed_len,
+                compressed.len()
+            );
+            request = request.header(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+            compressed
+        } else {
+            json
+        };
+        self.config
+            .metrics
+            .record_request(payload.len(), uncompressed_len, gzip_request);
+
+        let response = request.body(payload).send().await.map_err(map_send_error)?;
+
+        let status = response.status();
+        let mut headers = response.headers().clone();
+        let wire = response.bytes().await?;
+        let gzipped = headers
+            .get(CONTENT_ENCODING)
+            .is_some_and(|encoding| encoding.as_bytes().eq_ignore_ascii_case(b"gzip"));
+        let decoded = if gzipped {
+            headers.remove(CONTENT_ENCODING);
+            headers.remove(CONTENT_LENGTH);
+            gunzip(&wire, MAX_DECOMPRESSED_BYTES)
+                .map_err(|e| VoyageError::JsonError(format!("invalid gzip response body: {}", e)))?
+        } else {
+            wire.to_vec()
+        };
+        self.config
+            .metrics
+            .record_response(wire.len(), decoded.len());
+        let body = String::from_utf8_lossy(&decoded).into_owned();
+        if let Some(logged) = self.config.body_log_policy.render(&body) {
+            debug!("Response {} body: {}", status, logged);
+        }
+
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Returns how long to wait before retrying `result`, or `None` if it should not be retried.
+fn retry_delay(result: &Result<RawResponse, VoyageError>, backoff: Duration) -> Option<Duration> {
+    match result {
+        Ok(response) => match response.status {
+            StatusCode::TOO_MANY_REQUESTS => {
+                Some(retry_after(&response.headers).unwrap_or(backoff))
+            }
+            StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => Some(backoff),
+            _ => None,
+        },
+        Err(VoyageError::Timeout) => Some(backoff),
+        Err(VoyageError::RequestError(e)) if e.is_connect() => Some(backoff),
+        Err(_) => None,
+    }
+}
+
+/// Returns `true` if `result` suggests the endpoint is down rather than the request bad.
+fn is_outage<T>(result: &Result<(RawResponse, T), VoyageError>) -> bool {
+    match result {
+        Ok((response, _)) => response.status.is_server_error(),
+        Err(VoyageError::Timeout) => true,
+        Err(VoyageError::RequestError(e)) => e.is_connect() || e.is_timeout(),
+        Err(_) => false,
+    }
+}
+
+fn map_send_error(error: reqwest::Error) -> VoyageError {
+    if error.is_timeout() {
+        VoyageError::Timeout
+    } else {
+        VoyageError::RequestError(error)
+    }
+}
+
+/// Maps a non-success response to the matching `VoyageError`.
+pub fn error_for_status(response: &RawResponse) -> VoyageError {
+    let status = response.status;
+    let text = response.body.clone();
+    match status {
+        StatusCode::BAD_REQUEST => VoyageError::BadRequest { message: text },
+        StatusCode::UNAUTHORIZED => {
+            warn!("Unauthorized: Invalid API key");
+            VoyageError::Unauthorized
+        }
+        StatusCode::FORBIDDEN => VoyageError::Forbidden(text),
+        StatusCode::NOT_FOUND => VoyageError::NotFound(text),
+        StatusCode::TOO_MANY_REQUESTS => VoyageError::RateLimitExceeded {
+            reset_in: retry_after(&response.headers).unwrap_or(DEFAULT_RETRY_AFTER),
+        },
+        StatusCode::INTERNAL_SERVER_ERROR => VoyageError::InternalServerError { message: text },
+        StatusCode::SERVICE_UNAVAILABLE => VoyageError::ServiceUnavailable,
+        _ => VoyageError::ApiError(status, text),
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// `error` followed by its sources, which carry the detail of reqwest's builder errors.
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
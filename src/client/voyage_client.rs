@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use async_trait::async_trait;
@@ -6,29 +7,42 @@ use crate::{
         search::SearchRequest,
         embeddings::EmbeddingsRequestBuilder,
     },
+    bulk::BulkJob,
     client::{
-        embeddings_client::EmbeddingClient, 
+        cache::{CachedEmbeddingsClient, EmbeddingCache},
+        embed_stream::{self, EmbedStreamOptions, StreamedEmbedding},
+        embeddings_client::{EmbeddingClient, EmbeddingsClient},
+        isolation::{self, IsolatedBatch},
+        metrics::ClientMetrics,
         rerank_client::DefaultRerankClient,
         search_client::SearchClient, 
         RateLimiter, 
         RerankClient,
     },
-    config::VoyageConfig,
+    config::{ConfigLoader, VoyageConfig},
     errors::VoyageError,
     models::{
         embeddings::{EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse},
-        rerank::{RerankModel, RerankRequest, RerankResponse},
+        rerank::{RerankRequest, RerankResponse},
         search::{SearchModel, SearchQuery, SearchType},
     },
 };
 
 use super::SearchResult;
+use futures::Stream;
 use log::{debug, info};
 
 #[derive(Clone)]
 pub struct VoyageAiClientConfig {
     pub config: VoyageConfig,
     pub embeddings_client: Arc<EmbeddingClient>,
+    /// Serves `embed`, `embeddings`, `embed_text`, `embed_batch`, isolated and streaming
+    /// embedding, search and chained operations. The HTTP `embeddings_client` unless
+    /// replaced with [`VoyageAiClient::with_embeddings_client`]. Wrapped in `cache` when
+    /// the config has one.
+    pub embeddings: Arc<dyn EmbeddingsClient>,
+    /// Cache in front of `embeddings`, if `config.cache` is set.
+    pub cache: Option<EmbeddingCache>,
     pub rerank_client: Arc<DefaultRerankClient>,
     pub search_client: Arc<SearchClient>,
 }
@@ -64,56 +78,91 @@ pub struct VoyageAiClient {
     pub config: VoyageAiClientConfig,
 }
 
-impl Default for VoyageAiClient {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 impl VoyageAiClient {
+    /// Returns the Voyage HTTP embeddings client, which does not go through the cache.
     pub fn embeddings_client(&self) -> &Arc<EmbeddingClient> {
         &self.config.embeddings_client
     }
 
-    pub fn new_with_config(config: VoyageConfig) -> Self {
+    /// Returns the embeddings response cache, if one is configured.
+    pub fn cache(&self) -> Option<&EmbeddingCache> {
+        self.config.cache.as_ref()
+    }
+
+    /// Returns the traffic counters shared by the embeddings and rerank clients.
+    pub fn metrics(&self) -> &ClientMetrics {
+        &self.config.config.metrics
+    }
+
+    /// Creates a client from `config`, failing with `VoyageError::MissingApiKey` if it has
+    /// no key.
+    pub fn new_with_config(config: VoyageConfig) -> Result<Self, VoyageError> {
+        if config.api_key.is_empty() {
+            return Err(VoyageError::MissingApiKey);
+        }
         info!("Creating new VoyageAiClient");
-        let rate_limiter = Arc::new(RateLimiter::new());
+        let rate_limiter = Arc::new(RateLimiter::with_limits(config.rate_limits));
         let embeddings_client =
-            Arc::new(EmbeddingClient::new(config.clone(), rate_limiter.clone()));
+            Arc::new(EmbeddingClient::new(config.clone(), rate_limiter.clone())?);
         let rerank_client = Arc::new(DefaultRerankClient::new(
             config.clone(),
             rate_limiter.clone(),
-        ));
+        )?);
+        let embeddings: Arc<dyn EmbeddingsClient> = embeddings_client.clone();
         let search_client = Arc::new(SearchClient::new(
-            (*embeddings_client).clone(),
+            embeddings.clone(),
             (*rerank_client).clone(),
         ));
 
         let client_config = VoyageAiClientConfig {
             config,
             embeddings_client,
+            embeddings: embeddings.clone(),
+            cache: None,
             rerank_client,
             search_client,
         };
 
-        Self {
+        // Puts the HTTP client behind the cache, if one is configured.
+        Ok(Self {
             config: client_config,
         }
+        .with_embeddings_client(embeddings))
     }
 
-    pub fn new() -> Self {
+    /// Creates a client from the config file and environment; see [`ConfigLoader::standard`].
+    pub fn new() -> Result<Self, VoyageError> {
         info!("Creating new VoyageAiClient from environment");
-        let api_key = std::env::var("VOYAGE_API_KEY")
-            .or_else(|_| std::env::var("VOYAGEAI_API_KEY"))
-            .expect("API key must be set");
-        Self::new_with_config(VoyageConfig::new(api_key))
+        Self::new_with_config(ConfigLoader::standard().load()?)
     }
 
-    pub fn with_key(api_key: impl Into<String>) -> Self {
+    pub fn with_key(api_key: impl Into<String>) -> Result<Self, VoyageError> {
         info!("Creating new VoyageAiClient with provided key");
         Self::new_with_config(VoyageConfig::new(api_key.into()))
     }
 
+    /// Serves embeddings, including isolated and streaming embedding, search and chained
+    /// operations from `embeddings` instead of the HTTP client, e.g. to test search logic
+    /// offline. Jobs from [`bulk_job`](Self::bulk_job) embed with it as well.
+    ///
+    /// If the config has a `cache`, `embeddings` is put behind a new, empty cache.
+    pub fn with_embeddings_client(mut self, embeddings: Arc<dyn EmbeddingsClient>) -> Self {
+        self.config.cache = self.config.config.cache.map(EmbeddingCache::new);
+        let embeddings: Arc<dyn EmbeddingsClient> = match &self.config.cache {
+            Some(cache) => Arc::new(CachedEmbeddingsClient::with_cache(
+                embeddings,
+                cache.clone(),
+            )),
+            None => embeddings,
+        };
+        self.config.search_client = Arc::new(SearchClient::new(
+            embeddings.clone(),
+            (*self.config.rerank_client).clone(),
+        ));
+        self.config.embeddings = embeddings;
+        self
+    }
+
     pub async fn embed(
         &self,
         input: impl Into<EmbeddingsInput>,
@@ -128,13 +177,18 @@ impl VoyageAiClient {
             encoding_format: None,
         };
 
-        self.config
-            .embeddings_client
-            .create_embedding(&request)
+        self.embeddings(request)
             .await
             .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
     }
 
+    pub async fn create_embeddings(
+        &self,
+        input: impl Into<EmbeddingsInput>,
+    ) -> Result<EmbeddingsResponse, Box<dyn std::error::Error>> {
+        self.embed(input).await
+    }
+
     pub async fn embed_text(&self, text: impl Into<String>) -> Result<Vec<f32>, VoyageError> {
         let request = EmbeddingsRequestBuilder::new()
             .document(text)
@@ -148,6 +202,9 @@ impl VoyageAiClient {
     }
 
     pub async fn embed_batch(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, VoyageError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
         let request = EmbeddingsRequestBuilder::new()
             .documents(texts)
             .model(self.config.config.embedding_model)
@@ -157,6 +214,33 @@ impl VoyageAiClient {
         Ok(response.data.into_iter().map(|d| d.embedding).collect())
     }
 
+    /// Embeds a batch with per-input results, bisecting batches rejected with a 400;
+    /// see [`isolation::create_embedding_isolated`].
+    pub async fn embeddings_isolated(
+        &self,
+        request: EmbeddingsRequest,
+    ) -> Result<IsolatedBatch, VoyageError> {
+        isolation::create_embedding_isolated(&*self.config.embeddings, &request).await
+    }
+
+    /// Embeds a stream of `(id, text)` pairs; see [`embed_stream::embed_stream`].
+    pub fn embed_stream<S, I>(
+        &self,
+        inputs: S,
+        options: EmbedStreamOptions,
+    ) -> impl Stream<Item = (I, StreamedEmbedding)>
+    where
+        S: Stream<Item = (I, String)>,
+    {
+        embed_stream::embed_stream(self.config.embeddings.clone(), inputs, options)
+    }
+
+    /// Creates a [`BulkJob`] embedding `input` into `output` with this client's
+    /// embeddings client.
+    pub fn bulk_job(&self, input: impl Into<PathBuf>, output: impl Into<PathBuf>) -> BulkJob {
+        BulkJob::new(self.config.embeddings.clone(), input, output)
+    }
+
     pub async fn rerank(&self, request: RerankRequest) -> Result<RerankResponse, VoyageError> {
         debug!("Accessing RerankClient");
         self.config.rerank_client.rerank(&request).await
@@ -165,10 +249,9 @@ impl VoyageAiClient {
     pub async fn search(&self, request: SearchRequest) -> Result<Vec<SearchResult>, VoyageError> {
         debug!("Accessing SearchClient");
         self.config.search_client.search(&request).await
-            .map_err(VoyageError::from)
     }
 
-    pub fn chain(&self) -> ChainedOperationBuilder {
+    pub fn chain(&self) -> ChainedOperationBuilder<'_> {
         ChainedOperationBuilder::new(self)
     }
 
@@ -179,6 +262,7 @@ impl VoyageAiClient {
 
 pub struct ChainedOperationBuilder<'a> {
     client: &'a VoyageAiClient,
+    embeddings: Arc<dyn EmbeddingsClient>,
     embedded_docs: Option<Vec<Vec<f32>>>,
     reranked_docs: Option<Vec<String>>,
     search_results: Option<SearchRequest>,
@@ -188,12 +272,19 @@ impl<'a> ChainedOperationBuilder<'a> {
     pub fn new(client: &'a VoyageAiClient) -> Self {
         Self {
             client,
+            embeddings: client.config.embeddings.clone(),
             embedded_docs: None,
             reranked_docs: None,
             search_results: None,
         }
     }
 
+    /// Embeds documents in this chain with `embeddings` instead of the client's.
+    pub fn with_embeddings_client(mut self, embeddings: Arc<dyn EmbeddingsClient>) -> Self {
+        self.embeddings = embeddings;
+        self
+    }
+
     pub async fn embed_documents(mut self, input: impl Into<EmbeddingsInput>) -> Self {
         let request = EmbeddingsRequest {
             input: input.into(),
@@ -202,7 +293,7 @@ impl<'a> ChainedOperationBuilder<'a> {
             truncation: None,
             encoding_format: None,
         };
-        if let Ok(response) = self.client.embeddings(request).await {
+        if let Ok(response) = self.embeddings.create_embedding(&request).await {
             self.embedded_docs = Some(response.data.into_iter().map(|e| e.embedding).collect());
         }
         self
@@ -210,9 +301,17 @@ impl<'a> ChainedOperationBuilder<'a> {
 
     pub async fn rerank_documents(mut self, query: &str, documents: Vec<String>) -> Self {
         let rerank_request =
-            RerankRequest::new(query.to_string(), documents, RerankModel::Rerank2, None)
-                .expect("Invalid rerank request");
+            RerankRequest::new(
+                query.to_string(),
+                documents,
+                self.client.config.config.rerank_model,
+                None,
+            );
 
+        // Like the other steps, a failed rerank leaves the chain without reranked documents.
+        let Ok(rerank_request) = rerank_request else {
+            return self;
+        };
         if let Ok(response) = self.client.rerank(rerank_request).await {
             self.reranked_docs = Some(
                 response
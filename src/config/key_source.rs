@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use log::debug;
+
+use crate::config::ApiKey;
+use crate::errors::VoyageError;
+
+/// Where the API key comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeySource {
+    /// The key itself.
+    Value(ApiKey),
+    /// A file holding the key, such as a Docker secret. Surrounding whitespace is ignored.
+    File(PathBuf),
+    /// A shell command whose standard output is the key, e.g. a password manager CLI.
+    Command(String),
+}
+
+impl ApiKeySource {
+    /// Produces the key, failing with `VoyageError::MissingApiKey` if it is empty.
+    pub fn resolve(&self) -> Result<ApiKey, VoyageError> {
+        let key = match self {
+            ApiKeySource::Value(key) => key.clone(),
+            ApiKeySource::File(path) => {
+                debug!("Reading API key from {}", path.display());
+                let contents = fs::read_to_string(path).map_err(|e| {
+                    VoyageError::ConfigError(format!(
+                        "cannot read API key file {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                ApiKey::new(contents.trim())
+            }
+            ApiKeySource::Command(command) => {
+                debug!("Running API key command");
+                ApiKey::new(run_key_command(command)?)
+            }
+        };
+        if key.is_empty() {
+            return Err(VoyageError::MissingApiKey);
+        }
+        Ok(key)
+    }
+}
+
+fn run_key_command(command: &str) -> Result<String, VoyageError> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .map_err(|e| VoyageError::ConfigError(format!("cannot run API key command: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(VoyageError::ConfigError(format!(
+            "API key command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    String::from_utf8(output.stdout)
+        .map(|key| key.trim().to_string())
+        .map_err(|_| VoyageError::ConfigError("API key command printed invalid UTF-8".to_string()))
+}
@@ -0,0 +1,579 @@
+//! Layered configuration.
+//!
+//! Settings are merged from, lowest precedence first: built-in defaults, the `[default]`
+//! table of a TOML config file, the selected profile table of that file, `VOYAGE_*`
+//! environment variables, and explicit overrides such as `VoyageBuilder` calls.
+//!
+//! ```toml
+//! [default]
+//! embedding_model = "voyage-3-lite"
+//! timeout_secs = 30
+//!
+//! [staging]
+//! base_url = "https://voyage-proxy.staging.internal/v1"
+//! max_retries = 3
+//! ```
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use log::debug;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::client::cassette::{Cassette, CassetteMode};
+use crate::client::key_pool::{KeyPool, KeySelection};
+use crate::client::splitting::Pooling;
+use crate::config::{ApiKey, ApiKeySource, VoyageConfig};
+use crate::errors::VoyageError;
+use crate::models::embeddings::EmbeddingModel;
+use crate::models::rerank::RerankModel;
+
+/// Name of the profile whose settings apply under every other profile.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// One layer of settings. Fields left unset fall through to the layer below.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    pub api_key: Option<ApiKey>,
+    /// File holding the API key, used when `api_key` is not set.
+    pub api_key_file: Option<PathBuf>,
+    /// Command printing the API key, used when neither `api_key` nor `api_key_file` is set.
+    pub api_key_command: Option<String>,
+    /// Several keys to spread requests over; takes precedence over the fields above.
+    pub api_keys: Option<Vec<ApiKey>>,
+    /// How requests pick a key from `api_keys`.
+    pub key_selection: Option<KeySelection>,
+    pub base_url: Option<String>,
+    pub embedding_model: Option<EmbeddingModel>,
+    pub rerank_model: Option<RerankModel>,
+    pub timeout_secs: Option<f64>,
+    pub connect_timeout_secs: Option<f64>,
+    /// Proxy for every request, e.g. `http://proxy.corp.example:3128`.
+    pub proxy: Option<String>,
+    /// Hosts that bypass `proxy`.
+    pub no_proxy: Option<Vec<String>>,
+    /// Whether `HTTP_PROXY`, `HTTPS_PROXY` and similar variables are honoured.
+    pub system_proxy: Option<bool>,
+    /// PEM files with root certificates to trust in addition to the system store.
+    pub ca_certs: Option<Vec<PathBuf>>,
+    /// PEM client certificate for mutual TLS; requires `client_key`.
+    pub client_cert: Option<PathBuf>,
+    /// PEM (PKCS#8) private key for `client_cert`.
+    pub client_key: Option<PathBuf>,
+    /// Gzip request bodies of at least this many bytes.
+    pub gzip_threshold_bytes: Option<usize>,
+    /// Ask for gzipped responses.
+    pub accept_gzip: Option<bool>,
+    pub max_retries: Option<u32>,
+    pub retry_initial_delay_ms: Option<u64>,
+    pub retry_max_delay_ms: Option<u64>,
+    /// Consecutive failures that open an endpoint's circuit; setting any `circuit_*`
+    /// field enables the circuit breaker.
+    pub circuit_failure_threshold: Option<u32>,
+    pub circuit_open_secs: Option<f64>,
+    pub circuit_half_open_probes: Option<u32>,
+    pub embeddings_rpm: Option<u32>,
+    pub embeddings_tpm: Option<u32>,
+    pub rerank_rpm: Option<u32>,
+    pub rerank_tpm: Option<u32>,
+    pub dedupe_inputs: Option<bool>,
+    /// Split inputs longer than the context length into windows; setting any other
+    /// `split_*` field also enables it unless this is `false`.
+    pub split_long_inputs: Option<bool>,
+    pub split_window_tokens: Option<usize>,
+    pub split_overlap_tokens: Option<usize>,
+    pub split_pooling: Option<Pooling>,
+    /// Most embeddings responses kept in memory; `0` turns the cache off. Setting
+    /// `cache_ttl_secs` also enables it.
+    pub cache_capacity: Option<usize>,
+    /// How long a cached response may be served.
+    pub cache_ttl_secs: Option<f64>,
+    pub cassette: Option<PathBuf>,
+    pub cassette_mode: Option<CassetteMode>,
+}
+
+impl Settings {
+    /// Returns `self` with every field set in `top` replaced by `top`'s value.
+    ///
+    /// The API key fields (`api_key`, `api_key_file`, `api_key_command` and `api_keys`)
+    /// count as one: if `top` sets any of them, all of them and `key_selection` are taken
+    /// from `top`.
+    pub fn overlay(self, top: Settings) -> Settings {
+        let top_has_keys = top.has_key_source();
+        let key_layer = if top_has_keys { &top } else { &self };
+        let (api_key, api_key_file, api_key_command, api_keys) = (
+            key_layer.api_key.clone(),
+            key_layer.api_key_file.clone(),
+            key_layer.api_key_command.clone(),
+            key_layer.api_keys.clone(),
+        );
+        let key_selection = if top_has_keys {
+            top.key_selection
+        } else {
+            top.key_selection.or(self.key_selection)
+        };
+        Settings {
+            api_key,
+            api_key_file,
+            api_key_command,
+            api_keys,
+            key_selection,
+            base_url: top.base_url.or(self.base_url),
+            embedding_model: top.embedding_model.or(self.embedding_model),
+            rerank_model: top.rerank_model.or(self.rerank_model),
+            timeout_secs: top.timeout_secs.or(self.timeout_secs),
+            connect_timeout_secs: top.connect_timeout_secs.or(self.connect_timeout_secs),
+            proxy: top.proxy.or(self.proxy),
+            no_proxy: top.no_proxy.or(self.no_proxy),
+            system_proxy: top.system_proxy.or(self.system_proxy),
+            ca_certs: top.ca_certs.or(self.ca_certs),
+            client_cert: top.client_cert.or(self.client_cert),
+            client_key: top.client_key.or(self.client_key),
+            gzip_threshold_bytes: top.gzip_threshold_bytes.or(self.gzip_threshold_bytes),
+            accept_gzip: top.accept_gzip.or(self.accept_gzip),
+            max_retries: top.max_retries.or(self.max_retries),
+            retry_initial_delay_ms: top.retry_initial_delay_ms.or(self.retry_initial_delay_ms),
+            retry_max_delay_ms: top.retry_max_delay_ms.or(self.retry_max_delay_ms),
+            circuit_failure_threshold: top
+                .circuit_failure_threshold
+                .or(self.circuit_failure_threshold),
+            circuit_open_secs: top.circuit_open_secs.or(self.circuit_open_secs),
+            circuit_half_open_probes: top
+                .circuit_half_open_probes
+                .or(self.circuit_half_open_probes),
+            embeddings_rpm: top.embeddings_rpm.or(self.embeddings_rpm),
+            embeddings_tpm: top.embeddings_tpm.or(self.embeddings_tpm),
+            rerank_rpm: top.rerank_rpm.or(self.rerank_rpm),
+            rerank_tpm: top.rerank_tpm.or(self.rerank_tpm),
+            dedupe_inputs: top.dedupe_inputs.or(self.dedupe_inputs),
+            split_long_inputs: top.split_long_inputs.or(self.split_long_inputs),
+            split_window_tokens: top.split_window_tokens.or(self.split_window_tokens),
+            split_overlap_tokens: top.split_overlap_tokens.or(self.split_overlap_tokens),
+            split_pooling: top.split_pooling.or(self.split_pooling),
+            cache_capacity: top.cache_capacity.or(self.cache_capacity),
+            cache_ttl_secs: top.cache_ttl_secs.or(self.cache_ttl_secs),
+            cassette: top.cassette.or(self.cassette),
+            cassette_mode: top.cassette_mode.or(self.cassette_mode),
+        }
+    }
+
+    /// Returns where the API key should come from, if any key field is set.
+    pub fn api_key_source(&self) -> Option<ApiKeySource> {
+        if let Some(key) = &self.api_key {
+            Some(ApiKeySource::Value(key.clone()))
+        } else if let Some(path) = &self.api_key_file {
+            Some(ApiKeySource::File(path.clone()))
+        } else {
+            self.api_key_command.clone().map(ApiKeySource::Command)
+        }
+    }
+
+    fn has_key_source(&self) -> bool {
+        self.api_key.is_some()
+            || self.api_key_file.is_some()
+            || self.api_key_command.is_some()
+            || self.api_keys.is_some()
+    }
+
+    /// Reads settings from `VOYAGE_*` variables, e.g. `VOYAGE_BASE_URL` or
+    /// `VOYAGE_MAX_RETRIES`. `VOYAGEAI_API_KEY` is accepted as well as `VOYAGE_API_KEY`, and
+    /// `VOYAGE_API_KEY_FILE` or `VOYAGE_API_KEY_COMMAND` may supply the key instead.
+    /// `VOYAGE_API_KEYS` and `VOYAGE_NO_PROXY` are comma-separated lists and
+    /// `VOYAGE_CA_CERTS` a list of paths separated like `PATH`.
+    pub fn from_env_vars(vars: &HashMap<String, String>) -> Result<Settings, VoyageError> {
+        let get = |name: &str| vars.get(name).map(|v| v.trim()).filter(|v| !v.is_empty());
+        Ok(Settings {
+            api_key: get("VOYAGE_API_KEY")
+                .or_else(|| get("VOYAGEAI_API_KEY"))
+                .map(ApiKey::from),
+            api_key_file: get("VOYAGE_API_KEY_FILE").map(PathBuf::from),
+            api_key_command: get("VOYAGE_API_KEY_COMMAND").map(str::to_string),
+            api_keys: get("VOYAGE_API_KEYS").map(|keys| {
+                keys.split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(ApiKey::from)
+                    .collect()
+            }),
+            key_selection: parse_named(get, "VOYAGE_KEY_SELECTION")?,
+            base_url: get("VOYAGE_BASE_URL").map(str::to_string),
+            embedding_model: parse_named(get, "VOYAGE_EMBEDDING_MODEL")?,
+            rerank_model: parse_named(get, "VOYAGE_RERANK_MODEL")?,
+            timeout_secs: parse(get, "VOYAGE_TIMEOUT_SECS")?,
+            connect_timeout_secs: parse(get, "VOYAGE_CONNECT_TIMEOUT_SECS")?,
+            proxy: get("VOYAGE_PROXY").map(str::to_string),
+            no_proxy: get("VOYAGE_NO_PROXY").map(|hosts| {
+                hosts
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|host| !host.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            }),
+            system_proxy: parse(get, "VOYAGE_SYSTEM_PROXY")?,
+            ca_certs: get("VOYAGE_CA_CERTS").map(|paths| env::split_paths(paths).collect()),
+            client_cert: get("VOYAGE_CLIENT_CERT").map(PathBuf::from),
+            client_key: get("VOYAGE_CLIENT_KEY").map(PathBuf::from),
+            gzip_threshold_bytes: parse(get, "VOYAGE_GZIP_THRESHOLD_BYTES")?,
+            accept_gzip: parse(get, "VOYAGE_ACCEPT_GZIP")?,
+            max_retries: parse(get, "VOYAGE_MAX_RETRIES")?,
+            retry_initial_delay_ms: parse(get, "VOYAGE_RETRY_INITIAL_DELAY_MS")?,
+            retry_max_delay_ms: parse(get, "VOYAGE_RETRY_MAX_DELAY_MS")?,
+            circuit_failure_threshold: parse(get, "VOYAGE_CIRCUIT_FAILURE_THRESHOLD")?,
+            circuit_open_secs: parse(get, "VOYAGE_CIRCUIT_OPEN_SECS")?,
+            circuit_half_open_probes: parse(get, "VOYAGE_CIRCUIT_HALF_OPEN_PROBES")?,
+            embeddings_rpm: parse(get, "VOYAGE_EMBEDDINGS_RPM")?,
+            embeddings_tpm: parse(get, "VOYAGE_EMBEDDINGS_TPM")?,
+            rerank_rpm: parse(get, "VOYAGE_RERANK_RPM")?,
+            rerank_tpm: parse(get, "VOYAGE_RERANK_TPM")?,
+            dedupe_inputs: parse(get, "VOYAGE_DEDUPE_INPUTS")?,
+            split_long_inputs: parse(get, "VOYAGE_SPLIT_LONG_INPUTS")?,
+            split_window_tokens: parse(get, "VOYAGE_SPLIT_WINDOW_TOKENS")?,
+            split_overlap_tokens: parse(get, "VOYAGE_SPLIT_OVERLAP_TOKENS")?,
+            split_pooling: parse_named(get, "VOYAGE_SPLIT_POOLING")?,
+            cache_capacity: parse(get, "VOYAGE_CACHE_CAPACITY")?,
+            cache_ttl_secs: parse(get, "VOYAGE_CACHE_TTL_SECS")?,
+            cassette: get("VOYAGE_CASSETTE").map(PathBuf::from),
+            cassette_mode: parse_named(get, "VOYAGE_CASSETTE_MODE")?,
+        })
+    }
+
+    /// Applies these settings on top of `config`, resolving the API key source if set.
+    pub fn apply(self, mut config: VoyageConfig) -> Result<VoyageConfig, VoyageError> {
+        if let Some(keys) = self.api_keys {
+            let selection = self.key_selection.unwrap_or_default();
+            config = config.with_key_pool(KeyPool::new(keys, selection)?);
+        } else if let Some(source) = self.api_key_source() {
+            config.api_key = source.resolve()?;
+            config.key_pool = None;
+        }
+        if let Some(base_url) = self.base_url {
+            config.base_url = base_url;
+        }
+        if let Some(model) = self.embedding_model {
+            config.embedding_model = model;
+        }
+        if let Some(model) = self.rerank_model {
+            config.rerank_model = model;
+        }
+        if let Some(secs) = self.timeout_secs {
+            config.timeout = Some(seconds("timeout_secs", secs)?);
+        }
+        if let Some(secs) = self.connect_timeout_secs {
+            config.connect_timeout = Some(seconds("connect_timeout_secs", secs)?);
+        }
+        if let Some(url) = &self.proxy {
+            config.network = config.network.with_proxy(url)?;
+        }
+        if let Some(hosts) = self.no_proxy {
+            config.network.no_proxy = hosts;
+        }
+        if let Some(system_proxy) = self.system_proxy {
+            config.network.disable_system_proxy = !system_proxy;
+        }
+        for path in self.ca_certs.unwrap_or_default() {
+            config.network = config.network.with_root_certificate_file(path)?;
+        }
+        match (self.client_cert, self.client_key) {
+            (Some(cert), Some(key)) => {
+                config.network = config.network.with_client_identity_files(cert, key)?;
+            }
+            (None, None) => {}
+            _ => {
+                return Err(VoyageError::ConfigError(
+                    "client_cert and client_key must be set together".to_string(),
+                ))
+            }
+        }
+        if let Some(threshold) = self.gzip_threshold_bytes {
+            config.compression.gzip_threshold = Some(threshold);
+        }
+        if let Some(accept_gzip) = self.accept_gzip {
+            config.compression.accept_gzip = accept_gzip;
+        }
+        if let Some(max_retries) = self.max_retries {
+            config.retry.max_retries = max_retries;
+        }
+        if let Some(ms) = self.retry_initial_delay_ms {
+            config.retry.initial_delay = Duration::from_millis(ms);
+        }
+        if let Some(ms) = self.retry_max_delay_ms {
+            config.retry.max_delay = Duration::from_millis(ms);
+        }
+        if self.circuit_failure_threshold.is_some()
+            || self.circuit_open_secs.is_some()
+            || self.circuit_half_open_probes.is_some()
+        {
+            let mut breaker = config.circuit_breaker.unwrap_or_default();
+            if let Some(threshold) = self.circuit_failure_threshold {
+                breaker.failure_threshold = threshold;
+            }
+            if let Some(secs) = self.circuit_open_secs {
+                breaker.open_duration = seconds("circuit_open_secs", secs)?;
+            }
+            if let Some(probes) = self.circuit_half_open_probes {
+                breaker.half_open_probes = probes;
+            }
+            config.circuit_breaker = Some(breaker);
+        }
+        if let Some(rpm) = self.embeddings_rpm {
+            config.rate_limits.embeddings_rpm = rpm;
+        }
+        if let Some(tpm) = self.embeddings_tpm {
+            config.rate_limits.embeddings_tpm = tpm;
+        }
+        if let Some(rpm) = self.rerank_rpm {
+            config.rate_limits.rerank_rpm = rpm;
+        }
+        if let Some(tpm) = self.rerank_tpm {
+            config.rate_limits.rerank_tpm = tpm;
+        }
+        if let Some(dedupe_inputs) = self.dedupe_inputs {
+            config.dedupe_inputs = dedupe_inputs;
+        }
+        if self.split_long_inputs == Some(false) {
+            config.split_long_inputs = None;
+        } else if self.split_long_inputs.is_some()
+            || self.split_window_tokens.is_some()
+            || self.split_overlap_tokens.is_some()
+            || self.split_pooling.is_some()
+        {
+            let mut split = config.split_long_inputs.unwrap_or_default();
+            if let Some(tokens) = self.split_window_tokens {
+                split.window_tokens = Some(tokens);
+            }
+            if let Some(tokens) = self.split_overlap_tokens {
+                split.overlap_tokens = tokens;
+            }
+            if let Some(pooling) = self.split_pooling {
+                split.pooling = pooling;
+            }
+            config.split_long_inputs = Some(split);
+        }
+        if self.cache_capacity == Some(0) {
+            config.cache = None;
+        } else if self.cache_capacity.is_some() || self.cache_ttl_secs.is_some() {
+            let mut cache = config.cache.unwrap_or_default();
+            if let Some(capacity) = self.cache_capacity {
+                cache.capacity = capacity;
+            }
+            if let Some(secs) = self.cache_ttl_secs {
+                cache.ttl = Some(seconds("cache_ttl_secs", secs)?);
+            }
+            config.cache = Some(cache);
+        }
+        if let Some(path) = self.cassette {
+            let mode = self.cassette_mode.unwrap_or(CassetteMode::Record);
+            config.cassette = Some(Cassette::open(path, mode)?);
+        }
+        Ok(config)
+    }
+}
+
+/// Builds a [`VoyageConfig`] from defaults, a config file, the environment and overrides.
+#[derive(Clone, Default)]
+pub struct ConfigLoader {
+    file: Option<PathBuf>,
+    require_file: bool,
+    profile: Option<String>,
+    env: Option<HashMap<String, String>>,
+    overrides: Settings,
+}
+
+impl fmt::Debug for ConfigLoader {
+    // The environment may hold secrets, so only its size is shown.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigLoader")
+            .field("file", &self.file)
+            .field("require_file", &self.require_file)
+            .field("profile", &self.profile)
+            .field("env_vars", &self.env.as_ref().map(HashMap::len))
+            .field("overrides", &self.overrides)
+            .finish()
+    }
+}
+
+impl ConfigLoader {
+    /// Creates a loader that uses only the built-in defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a loader that reads [`default_config_path`] if it exists and the
+    /// process environment.
+    pub fn standard() -> Self {
+        Self {
+            file: default_config_path(),
+            require_file: false,
+            ..Self::default()
+        }
+        .with_env()
+    }
+
+    /// Reads settings from the TOML file at `path`, which must exist.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self.require_file = true;
+        self
+    }
+
+    /// Selects a profile from the config file. Without this, `VOYAGE_PROFILE` is used
+    /// if set, and otherwise only the `[default]` table applies.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Reads settings from the process environment.
+    pub fn with_env(self) -> Self {
+        self.with_env_vars(env::vars())
+    }
+
+    /// Reads settings from the given variables instead of the process environment.
+    pub fn with_env_vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = Some(
+            vars.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Applies `overrides` above every other layer.
+    pub fn with_overrides(mut self, overrides: Settings) -> Self {
+        self.overrides = std::mem::take(&mut self.overrides).overlay(overrides);
+        self
+    }
+
+    /// Returns the profile that will be used, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref().or_else(|| {
+            self.env
+                .as_ref()?
+                .get("VOYAGE_PROFILE")
+                .map(String::as_str)
+                .filter(|p| !p.is_empty())
+        })
+    }
+
+    /// Merges every layer into one set of settings.
+    pub fn settings(&self) -> Result<Settings, VoyageError> {
+        let mut settings = Settings::default();
+
+        if let Some(path) = &self.file {
+            if path.exists() || self.require_file {
+                settings = settings.overlay(self.file_settings(path)?);
+            } else if let Some(profile) = self.profile() {
+                return Err(VoyageError::ConfigError(format!(
+                    "profile '{}' requested but {} does not exist",
+                    profile,
+                    path.display()
+                )));
+            }
+        } else if let Some(profile) = self.profile() {
+            return Err(VoyageError::ConfigError(format!(
+                "profile '{}' requested but no config file is set",
+                profile
+            )));
+        }
+
+        if let Some(vars) = &self.env {
+            settings = settings.overlay(Settings::from_env_vars(vars)?);
+        }
+        Ok(settings.overlay(self.overrides.clone()))
+    }
+
+    /// Builds the config. Fails with `VoyageError::MissingApiKey` if no layer provides a key.
+    pub fn load(&self) -> Result<VoyageConfig, VoyageError> {
+        let config = self
+            .settings()?
+            .apply(VoyageConfig::new(ApiKey::default()))?;
+        if config.api_key.is_empty() {
+            return Err(VoyageError::MissingApiKey);
+        }
+        Ok(config)
+    }
+
+    fn file_settings(&self, path: &Path) -> Result<Settings, VoyageError> {
+        let contents = fs::read_to_string(path).map_err(|e| {
+            VoyageError::ConfigError(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        let mut profiles: HashMap<String, Settings> = toml::from_str(&contents)
+            .map_err(|e| VoyageError::ConfigError(format!("{}: {}", path.display(), e)))?;
+        debug!(
+            "Loaded config file {} with profiles {:?}",
+            path.display(),
+            profiles.keys().collect::<Vec<_>>()
+        );
+
+        let base = profiles.remove(DEFAULT_PROFILE).unwrap_or_default();
+        match self.profile() {
+            None | Some(DEFAULT_PROFILE) => Ok(base),
+            Some(name) => match profiles.remove(name) {
+                Some(profile) => Ok(base.overlay(profile)),
+                None => Err(VoyageError::ConfigError(format!(
+                    "profile '{}' not found in {}",
+                    name,
+                    path.display()
+                ))),
+            },
+        }
+    }
+}
+
+/// Returns the config file location: `$VOYAGE_CONFIG`, else
+/// `$XDG_CONFIG_HOME/voyageai/config.toml`, else `~/.config/voyageai/config.toml`.
+pub fn default_config_path() -> Option<PathBuf> {
+    let non_empty = |name: &str| env::var_os(name).filter(|v| !v.is_empty());
+    if let Some(path) = non_empty("VOYAGE_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("voyageai").join("config.toml"))
+}
+
+fn parse<'a, T>(get: impl Fn(&str) -> Option<&'a str>, name: &str) -> Result<Option<T>, VoyageError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| VoyageError::ConfigError(format!("{}={}: {}", name, value, e)))
+        })
+        .transpose()
+}
+
+/// Parses a value by its serialized name, e.g. `voyage-3` or `replay_only`.
+fn parse_named<'a, T: DeserializeOwned>(
+    get: impl Fn(&str) -> Option<&'a str>,
+    name: &str,
+) -> Result<Option<T>, VoyageError> {
+    get(name)
+        .map(|value| {
+            serde_json::from_value(serde_json::Value::String(value.to_string()))
+                .map_err(|e| VoyageError::ConfigError(format!("{}={}: {}", name, value, e)))
+        })
+        .transpose()
+}
+
+fn seconds(field: &str, secs: f64) -> Result<Duration, VoyageError> {
+    Duration::try_from_secs_f64(secs)
+        .map_err(|e| VoyageError::ConfigError(format!("{} = {}: {}", field, secs, e)))
+}
@@ -1,3 +1,11 @@
+mod key_source;
+mod loader;
+mod network;
+mod secret;
 mod voyage_config;
 
+pub use key_source::ApiKeySource;
+pub use loader::{default_config_path, ConfigLoader, Settings, DEFAULT_PROFILE};
+pub use network::NetworkConfig;
+pub use secret::{ApiKey, BodyLogPolicy};
 pub use voyage_config::VoyageConfig;
@@ -0,0 +1,115 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use reqwest::{Certificate, ClientBuilder, Identity, NoProxy, Proxy, Url};
+
+use crate::errors::VoyageError;
+
+/// Proxy and TLS settings for the HTTP client.
+///
+/// Files are read when they are added, so a missing or malformed certificate is reported
+/// while the config is built rather than on the first request.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfig {
+    /// Proxy every request goes through, e.g. `http://proxy.corp.example:3128`.
+    pub proxy: Option<Url>,
+    /// Hosts that bypass `proxy`, in `NO_PROXY` syntax (`internal.example`, `10.0.0.0/8`).
+    pub no_proxy: Vec<String>,
+    /// Ignore `HTTP_PROXY`, `HTTPS_PROXY` and the other system proxy settings.
+    pub disable_system_proxy: bool,
+    /// Root certificates trusted in addition to the system store.
+    pub root_certificates: Vec<Certificate>,
+    /// Client certificate and key presented for mutual TLS.
+    pub identity: Option<Identity>,
+}
+
+impl NetworkConfig {
+    /// Sends every request through the proxy at `url`.
+    pub fn with_proxy(mut self, url: &str) -> Result<Self, VoyageError> {
+        let parsed = Url::parse(url).map_err(|e| proxy_error(url, &e))?;
+        Proxy::all(parsed.clone()).map_err(|e| proxy_error(url, &e))?;
+        self.proxy = Some(parsed);
+        Ok(self)
+    }
+
+    /// Lets requests to `hosts` bypass the proxy.
+    pub fn with_no_proxy<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.no_proxy.extend(hosts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Ignores proxies configured in the environment.
+    pub fn without_system_proxy(mut self) -> Self {
+        self.disable_system_proxy = true;
+        self
+    }
+
+    /// Trusts every certificate in the PEM file at `path`.
+    pub fn with_root_certificate_file(
+        mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, VoyageError> {
+        let path = path.as_ref();
+        let certificates =
+            Certificate::from_pem_bundle(&read(path)?).map_err(|e| tls_error(path, &e))?;
+        if certificates.is_empty() {
+            return Err(tls_error(path, &"no certificates found"));
+        }
+        self.root_certificates.extend(certificates);
+        Ok(self)
+    }
+
+    /// Presents the certificate at `cert` with the PKCS#8 private key at `key`, both PEM.
+    pub fn with_client_identity_files(
+        mut self,
+        cert: impl AsRef<Path>,
+        key: impl AsRef<Path>,
+    ) -> Result<Self, VoyageError> {
+        let (cert, key) = (cert.as_ref(), key.as_ref());
+        let identity =
+            Identity::from_pkcs8_pem(&read(cert)?, &read(key)?).map_err(|e| tls_error(cert, &e))?;
+        self.identity = Some(identity);
+        Ok(self)
+    }
+
+    /// Applies these settings to `builder`.
+    pub(crate) fn configure(
+        &self,
+        mut builder: ClientBuilder,
+    ) -> Result<ClientBuilder, VoyageError> {
+        if self.disable_system_proxy {
+            builder = builder.no_proxy();
+        }
+        if let Some(url) = &self.proxy {
+            let proxy = Proxy::all(url.clone())
+                .map_err(|e| proxy_error(url.as_str(), &e))?
+                .no_proxy(NoProxy::from_string(&self.no_proxy.join(",")));
+            builder = builder.proxy(proxy);
+        }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if let Some(identity) = &self.identity {
+            builder = builder.identity(identity.clone());
+        }
+        Ok(builder)
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, VoyageError> {
+    fs::read(path)
+        .map_err(|e| VoyageError::ConfigError(format!("cannot read {}: {}", path.display(), e)))
+}
+
+fn proxy_error(url: &str, error: &dyn fmt::Display) -> VoyageError {
+    VoyageError::ConfigError(format!("invalid proxy URL {}: {}", url, error))
+}
+
+fn tls_error(path: &Path, error: &dyn fmt::Display) -> VoyageError {
+    VoyageError::ConfigError(format!("{}: {}", path.display(), error))
+}
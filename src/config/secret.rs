@@ -0,0 +1,104 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+/// An API key that never shows up in `Debug` or `Display` output.
+///
+/// Use [`ApiKey::expose`] where the raw value is genuinely needed, such as the
+/// `Authorization` header.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    /// Wraps a raw key.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    /// Returns the raw key.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns a short, log-safe hint: the last four characters of keys long enough
+    /// that this reveals nothing useful, `[REDACTED]` otherwise.
+    pub fn hint(&self) -> String {
+        let chars: Vec<char> = self.0.chars().collect();
+        if chars.len() >= 16 {
+            format!("…{}", chars[chars.len() - 4..].iter().collect::<String>())
+        } else {
+            "[REDACTED]".to_string()
+        }
+    }
+
+    /// Returns `true` if no key was provided.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ApiKey({})", self)
+    }
+}
+
+impl fmt::Display for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("<unset>")
+        } else {
+            f.write_str("[REDACTED]")
+        }
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self(key.to_string())
+    }
+}
+
+/// How much of request and response bodies the clients write to the log.
+///
+/// Bodies carry customer text and embeddings, so anything beyond
+/// [`BodyLogPolicy::LengthsOnly`] should stay out of production.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyLogPolicy {
+    /// Bodies are not logged at all.
+    Off,
+    /// Only the body size in bytes is logged.
+    #[default]
+    LengthsOnly,
+    /// At most this many characters of each body are logged.
+    Truncated(usize),
+    /// Bodies are logged in full.
+    Full,
+}
+
+impl BodyLogPolicy {
+    /// Renders `body` for the log, or `None` if nothing should be logged.
+    pub fn render(&self, body: &str) -> Option<String> {
+        match *self {
+            BodyLogPolicy::Off => None,
+            BodyLogPolicy::LengthsOnly => Some(format!("<{} bytes>", body.len())),
+            BodyLogPolicy::Truncated(limit) => {
+                let total = body.chars().count();
+                if total <= limit {
+                    Some(body.to_string())
+                } else {
+                    let head: String = body.chars().take(limit).collect();
+                    Some(format!("{}... <{} more chars>", head, total - limit))
+                }
+            }
+            BodyLogPolicy::Full => Some(body.to_string()),
+        }
+    }
+}
@@ -1,5 +1,18 @@
+use crate::client::cache::CacheConfig;
+use crate::client::cassette::Cassette;
+use crate::client::circuit_breaker::{CircuitBreakerConfig, CircuitListener, CircuitTransition};
+use crate::client::client_limiter::RateLimits;
+use crate::client::compression::CompressionConfig;
+use crate::client::interceptor::Interceptor;
+use crate::client::key_pool::KeyPool;
+use crate::client::metrics::ClientMetrics;
+use crate::client::retry::RetryConfig;
+use crate::client::splitting::SplitConfig;
+use crate::config::{ApiKey, BodyLogPolicy, NetworkConfig};
 use crate::models::{embeddings::EmbeddingModel, search::SearchModel, RerankModel};
 use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Deserialize)]
@@ -17,19 +30,67 @@ impl Default for Model {
 
 #[derive(Debug, Clone, Default)]
 pub struct VoyageConfig {
-    pub api_key: String,
+    pub api_key: ApiKey,
     pub base_url: String,
     pub search_model: SearchModel,
     pub embedding_model: EmbeddingModel,
+    pub rerank_model: RerankModel,
+    /// Overall time limit for each HTTP request.
+    pub timeout: Option<Duration>,
+    /// Time limit for establishing a connection.
+    pub connect_timeout: Option<Duration>,
+    /// Proxy, extra root certificates and client identity.
+    pub network: NetworkConfig,
+    /// Gzip for request and response bodies.
+    pub compression: CompressionConfig,
+    /// Traffic counters, shared by every client built from this config.
+    pub metrics: ClientMetrics,
+    pub retry: RetryConfig,
+    /// Fail fast per endpoint after repeated failures; off when `None`.
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Callbacks run when a circuit changes state.
+    pub circuit_listeners: Vec<CircuitListener>,
+    pub rate_limits: RateLimits,
+    /// Collapse identical inputs in a batch into a single API input.
+    pub dedupe_inputs: bool,
+    /// Embed inputs longer than the context length in windows and pool them.
+    pub split_long_inputs: Option<SplitConfig>,
+    /// Serve repeated embeddings requests from memory; off when `None`.
+    pub cache: Option<CacheConfig>,
+    /// Record or replay API interactions instead of always calling the API.
+    pub cassette: Option<Cassette>,
+    /// Hooks run around every request, in order.
+    pub interceptors: Vec<Arc<dyn Interceptor>>,
+    /// How much of request and response bodies is written to the log.
+    pub body_log_policy: BodyLogPolicy,
+    /// Several keys to spread requests over; `api_key` is then the pool's first key.
+    pub key_pool: Option<KeyPool>,
 }
 
 impl VoyageConfig {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
         Self {
-            api_key,
+            api_key: api_key.into(),
             base_url: "https://api.voyageai.com/v1".to_string(),
             search_model: SearchModel::default(),
             embedding_model: EmbeddingModel::default(),
+            rerank_model: RerankModel::default(),
+            timeout: None,
+            connect_timeout: None,
+            network: NetworkConfig::default(),
+            compression: CompressionConfig::default(),
+            metrics: ClientMetrics::default(),
+            retry: RetryConfig::default(),
+            circuit_breaker: None,
+            circuit_listeners: Vec::new(),
+            rate_limits: RateLimits::default(),
+            dedupe_inputs: false,
+            split_long_inputs: None,
+            cache: None,
+            cassette: None,
+            interceptors: Vec::new(),
+            body_log_policy: BodyLogPolicy::default(),
+            key_pool: None,
         }
     }
 
@@ -38,8 +99,97 @@ impl VoyageConfig {
         self
     }
 
+    pub fn with_embedding_model(mut self, model: EmbeddingModel) -> Self {
+        self.embedding_model = model;
+        self
+    }
+
+    pub fn with_rerank_model(mut self, model: RerankModel) -> Self {
+        self.rerank_model = model;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_network(mut self, network: NetworkConfig) -> Self {
+        self.network = network;
+        self
+    }
+
+    pub fn with_compression(mut self, compression: CompressionConfig) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    pub fn with_circuit_listener(
+        mut self,
+        listener: impl Fn(&CircuitTransition) + Send + Sync + 'static,
+    ) -> Self {
+        self.circuit_listeners.push(CircuitListener::new(listener));
+        self
+    }
+
+    pub fn with_rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    pub fn with_dedupe_inputs(mut self, dedupe_inputs: bool) -> Self {
+        self.dedupe_inputs = dedupe_inputs;
+        self
+    }
+
+    pub fn with_split_long_inputs(mut self, split: SplitConfig) -> Self {
+        self.split_long_inputs = Some(split);
+        self
+    }
+
+    pub fn with_cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    pub fn with_key_pool(mut self, key_pool: KeyPool) -> Self {
+        self.api_key = key_pool.primary().clone();
+        self.key_pool = Some(key_pool);
+        self
+    }
+
+    pub fn with_body_log_policy(mut self, policy: BodyLogPolicy) -> Self {
+        self.body_log_policy = policy;
+        self
+    }
+
     pub fn api_key(&self) -> &str {
-        &self.api_key
+        self.api_key.expose()
     }
 }
 
@@ -39,7 +39,12 @@ pub enum VoyageError {
     #[error("Service Unavailable (503)")]
     ServiceUnavailable,
 
-    #[error("Missing API key")]
+    #[error("Request timed out")]
+    Timeout,
+
+    #[error(
+        "Missing API key: set VOYAGE_API_KEY or VOYAGE_API_KEY_FILE, or api_key in the config file"
+    )]
     MissingApiKey,
 
     #[error("JSON serialization/deserialization error: {0}")]
@@ -60,6 +65,16 @@ pub enum VoyageError {
     #[error("Input list too long: maximum of 128 texts allowed")]
     InputListTooLong,
 
+    #[error("Input {0} is empty")]
+    EmptyInput(usize),
+
+    #[error("Input {index} is too long: about {tokens} tokens (context length: {limit})")]
+    InputTooLong {
+        index: usize,
+        tokens: usize,
+        limit: usize,
+    },
+
     #[error("Total tokens exceed model limit: {0} tokens (limit: {1})")]
     TokenLimitExceeded(usize, usize),
 
@@ -74,6 +89,21 @@ pub enum VoyageError {
 
     #[error("No results found")]
     NoResults,
+
+    #[error("Configuration error: {0}")]
+    ConfigError(String),
+
+    #[error("Cassette miss: {0}")]
+    CassetteMiss(String),
+
+    #[error("Bulk job error: {0}")]
+    BulkJobError(String),
+
+    #[error("Circuit open for {endpoint}: failing fast, next probe in {retry_in:?}")]
+    CircuitOpen { endpoint: String, retry_in: Duration },
+
+    #[error("Runtime error: {0}")]
+    RuntimeError(String),
 }
 
 impl From<serde_json::Error> for VoyageError {
@@ -98,7 +128,13 @@ impl From<&str> for VoyageError {
 
 impl From<ValidationError> for VoyageError {
     fn from(err: ValidationError) -> Self {
-        VoyageError::BuilderError(err.to_string())
+        match err {
+            ValidationError::QueryDocumentTokenLimitExceeded { tokens, limit, .. } => {
+                VoyageError::QueryDocumentTokenLimitExceeded(tokens, limit)
+            }
+            ValidationError::EmptyDocument { index } => VoyageError::EmptyInput(index),
+            err => VoyageError::BuilderError(err.to_string()),
+        }
     }
 }
 
@@ -127,10 +163,39 @@ pub enum VoyageBuilderError {
 
     #[error("Missing Voyage client")]
     MissingVoyage,
+
+    #[error("Input {0} is empty")]
+    EmptyInput(usize),
+
+    #[error("Input {index} is too long: about {tokens} tokens (context length: {limit})")]
+    InputTooLong {
+        index: usize,
+        tokens: usize,
+        limit: usize,
+    },
+
+    #[error("Total tokens exceed model limit: {0} tokens (limit: {1})")]
+    TokenLimitExceeded(usize, usize),
 }
 
 impl From<VoyageBuilderError> for VoyageError {
     fn from(error: VoyageBuilderError) -> Self {
-        VoyageError::BuilderError(error.to_string())
+        match error {
+            VoyageBuilderError::InputListTooLong => VoyageError::InputListTooLong,
+            VoyageBuilderError::EmptyInput(index) => VoyageError::EmptyInput(index),
+            VoyageBuilderError::InputTooLong {
+                index,
+                tokens,
+                limit,
+            } => VoyageError::InputTooLong {
+                index,
+                tokens,
+                limit,
+            },
+            VoyageBuilderError::TokenLimitExceeded(tokens, limit) => {
+                VoyageError::TokenLimitExceeded(tokens, limit)
+            }
+            error => VoyageError::BuilderError(error.to_string()),
+        }
     }
 }
@@ -1,4 +1,6 @@
+pub mod backends;
 pub mod builder;
+pub mod bulk;
 pub mod client;
 pub mod config;
 pub mod errors;
@@ -9,11 +11,13 @@ pub use builder::{
     embeddings::EmbeddingsRequestBuilder, rerank::RerankRequestBuilder,
     search::SearchRequestBuilder, voyage::VoyageBuilder,
 };
+pub use client::interceptor::{InterceptAction, InterceptedRequest, Interceptor};
 pub use client::voyage_client::VoyageAiClient;
-pub use config::VoyageConfig;
+pub use config::{ApiKey, BodyLogPolicy, VoyageConfig};
 pub use errors::{VoyageBuilderError, VoyageError};
 pub use models::{
     embeddings::{EmbeddingModel, EmbeddingsInput, InputType},
+    hashing::StableHash,
     rerank::{RerankModel, RerankRequest, RerankResponse},
     search::{SearchModel, SearchType},
 };
@@ -1,12 +1,23 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use voyageai::config::ConfigLoader;
 use voyageai::{
-    EmbeddingModel, EmbeddingsInput, EmbeddingsRequestBuilder, InputType, RerankModel,
-    RerankRequest, VoyageAiClient, VoyageConfig,
+    EmbeddingModel, EmbeddingsInput, EmbeddingsRequestBuilder, InputType, RerankRequest,
+    VoyageAiClient,
 };
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Cli {
+    /// Config file profile to use (defaults to $VOYAGE_PROFILE)
+    #[clap(long, global = true)]
+    profile: Option<String>,
+
+    /// Config file to read instead of ~/.config/voyageai/config.toml
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+
     #[clap(subcommand)]
     command: Commands,
 }
@@ -19,9 +30,9 @@ enum Commands {
         #[clap(short, long)]
         text: Vec<String>,
 
-        /// Model to use for embeddings
-        #[clap(short, long, default_value = "voyage-3")]
-        model: String,
+        /// Model to use for embeddings (defaults to the configured model)
+        #[clap(short, long)]
+        model: Option<String>,
     },
     /// Rerank documents based on a query
     Rerank {
@@ -37,6 +48,36 @@ enum Commands {
         #[clap(short, long)]
         top_k: Option<usize>,
     },
+    /// Embed a JSONL or CSV file into a JSONL file, resuming from its checkpoint
+    Bulk {
+        /// Input file (.jsonl or .csv)
+        #[clap(short, long)]
+        input: PathBuf,
+
+        /// Output JSONL file
+        #[clap(short, long)]
+        output: PathBuf,
+
+        /// Field holding the record id
+        #[clap(long, default_value = "id")]
+        id_field: String,
+
+        /// Field holding the text to embed
+        #[clap(long, default_value = "text")]
+        text_field: String,
+
+        /// Records per request
+        #[clap(long, default_value_t = 128)]
+        batch_size: usize,
+
+        /// Requests in flight at once
+        #[clap(long, default_value_t = 4)]
+        concurrency: usize,
+
+        /// Write records the API rejects to this file instead of stopping
+        #[clap(long)]
+        dead_letter: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -44,10 +85,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse CLI arguments
     let cli = Cli::parse();
 
-    // Get API key from environment
-    let api_key = std::env::var("VOYAGE_API_KEY").expect("VOYAGE_API_KEY must be set");
-    let config = VoyageConfig::new(api_key);
-    let client = VoyageAiClient::new_with_config(config);
+    // Merge config file, environment and flags
+    let mut loader = ConfigLoader::standard();
+    if let Some(path) = &cli.config {
+        loader = loader.with_file(path);
+    }
+    if let Some(profile) = &cli.profile {
+        loader = loader.with_profile(profile);
+    }
+    let client = match loader.load().and_then(VoyageAiClient::new_with_config) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    };
 
     handle_command(&cli, &client).await?;
     Ok(())
@@ -56,7 +108,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 async fn handle_command(cli: &Cli, client: &VoyageAiClient) -> Result<(), Box<dyn std::error::Error>> {
     match cli.command {
         Commands::Embed { ref text, ref model } => {
-            let model = match model.as_str() {
+            let configured = client.config.config.embedding_model.as_str();
+            let model = match model.as_deref().unwrap_or(configured) {
                 "voyage-3" => EmbeddingModel::Voyage3,
                 "voyage-3-lite" => EmbeddingModel::Voyage3Lite,
                 "voyage-finance-2" => EmbeddingModel::VoyageFinance2,
@@ -85,13 +138,17 @@ async fn handle_command(cli: &Cli, client: &VoyageAiClient) -> Result<(), Box<dy
             ref documents,
             top_k,
         } => {
-            let request = RerankRequest::new(query.clone(), documents.clone(), RerankModel::Rerank2, top_k)
-                .expect("Failed to create rerank request");
+            let model = client.config.config.rerank_model;
+            let request = RerankRequest::new(query.clone(), documents.clone(), model, top_k);
+            let request = match request {
+                Ok(request) => request,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    std::process::exit(2);
+                }
+            };
 
-            let response = client
-                .rerank(request)
-                .await
-                .expect("Failed to rerank documents");
+            let response = client.rerank(request).await?;
 
             println!("\nReranked documents by relevance:");
             for result in response.data.iter() {
@@ -102,5 +159,45 @@ async fn handle_command(cli: &Cli, client: &VoyageAiClient) -> Result<(), Box<dy
             }
             Ok(())
         }
+
+        Commands::Bulk {
+            ref input,
+            ref output,
+            ref id_field,
+            ref text_field,
+            batch_size,
+            concurrency,
+            ref dead_letter,
+        } => {
+            let mut job = client.bulk_job(input, output);
+            if let Some(path) = dead_letter {
+                job = job.with_dead_letter(path);
+            }
+            let report = job
+                .with_id_field(id_field.as_str())
+                .with_text_field(text_field.as_str())
+                .with_batch_size(batch_size)
+                .with_concurrency(concurrency)
+                .with_progress(|p| {
+                    let total = p.records_total.map_or("?".to_string(), |t| t.to_string());
+                    let eta = p.eta.map_or("?".to_string(), |eta| format!("{}s", eta.as_secs()));
+                    eprintln!(
+                        "{}/{} records, {} tokens, {:.1} records/s, eta {}",
+                        p.records_done, total, p.tokens, p.throughput, eta
+                    );
+                })
+                .run()
+                .await?;
+
+            println!(
+                "Embedded {} records ({} resumed, {} rejected) using {} tokens in {:.1}s",
+                report.records,
+                report.resumed_from,
+                report.failed,
+                report.tokens,
+                report.elapsed.as_secs_f64()
+            );
+            Ok(())
+        }
     }
 }
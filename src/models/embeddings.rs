@@ -1,3 +1,4 @@
+use crate::models::metadata::ResponseMetadata;
 use crate::VoyageError;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
@@ -10,7 +11,7 @@ pub enum InputType {
     Document,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum EmbeddingsInput {
     Single(String),
@@ -45,7 +46,7 @@ impl From<Vec<String>> for EmbeddingsInput {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EmbeddingsRequest {
     pub input: EmbeddingsInput,
     pub model: EmbeddingModel,
@@ -57,7 +58,7 @@ pub struct EmbeddingsRequest {
     pub encoding_format: Option<EncodingFormat>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EmbeddingsResponse {
     /// The type of object returned.
     #[serde(default)]
@@ -69,23 +70,54 @@ pub struct EmbeddingsResponse {
     pub model: String,
     /// Usage statistics for the request.
     pub usage: Usage,
+    /// What in-batch deduplication saved, if it was enabled and found duplicates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedupe: Option<DedupeStats>,
+    /// Inputs that were split into windows and pooled; empty unless splitting is enabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub split: Vec<SplitInput>,
+    /// Redacted label of the API key that served the request, e.g. `#1 …9f2c`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub served_by: Option<String>,
+    /// Latency, retries, request id and rate-limit headers of the call. Responses
+    /// combined from several calls carry the last call's metadata.
+    #[serde(default, skip_serializing_if = "ResponseMetadata::is_empty")]
+    pub metadata: ResponseMetadata,
+}
+
+/// Savings reported when identical inputs were collapsed before sending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DedupeStats {
+    /// Number of inputs that were not sent because an identical input was.
+    pub inputs_saved: usize,
+    /// Estimated number of tokens that were not sent.
+    pub tokens_saved: u32,
+}
+
+/// An input longer than the window, embedded as several windows and pooled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SplitInput {
+    /// Position of the input in the request.
+    pub index: usize,
+    /// Number of windows it was split into.
+    pub windows: usize,
 }
 
 /// Usage statistics for an embedding request.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Usage {
     /// The total number of tokens used in the request.
     pub total_tokens: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EmbeddingData {
     pub object: String,
     pub embedding: Vec<f32>,
     pub index: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum EncodingFormat {
     #[serde(rename = "float")]
     Float,
@@ -110,6 +142,17 @@ pub enum EmbeddingModel {
 }
 
 impl EmbeddingModel {
+    /// Returns the model name used by the API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Voyage3 => "voyage-3",
+            Self::Voyage3Lite => "voyage-3-lite",
+            Self::VoyageFinance2 => "voyage-finance-2",
+            Self::VoyageMultilingual2 => "voyage-multilingual-2",
+            Self::VoyageLaw2 => "voyage-law-2",
+        }
+    }
+
     /// Returns the maximum context length for the model
     pub fn max_context_length(&self) -> usize {
         match self {
@@ -130,9 +173,10 @@ impl EmbeddingModel {
     /// Returns the embedding dimension for the model
     pub fn embedding_dimension(&self) -> usize {
         match self {
-            Self::Voyage3 => 512,
-            Self::Voyage3Lite => 256,
-            Self::VoyageFinance2 | Self::VoyageMultilingual2 | Self::VoyageLaw2 => 1024,
+            Self::Voyage3Lite => 512,
+            Self::Voyage3 | Self::VoyageFinance2 | Self::VoyageMultilingual2 | Self::VoyageLaw2 => {
+                1024
+            }
         }
     }
 }
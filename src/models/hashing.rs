@@ -0,0 +1,68 @@
+//! Stable hashing of requests, for keying caches, cassettes and job manifests.
+//!
+//! The hash is FNV-1a over the request's JSON wire form, so it is the same across
+//! processes, platforms and compiler versions, unlike [`std::hash::Hash`] with the
+//! standard library's hashers. Optional fields that are unset are left out of the JSON,
+//! so adding a new optional field does not change the hash of existing requests.
+
+use std::io;
+
+use serde::Serialize;
+
+use crate::errors::VoyageError;
+use crate::models::embeddings::EmbeddingsRequest;
+use crate::models::rerank::RerankRequest;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A hash of a request that is stable across runs, usable as a persistent key.
+pub trait StableHash: Serialize {
+    /// 64-bit FNV-1a hash of the request's JSON form.
+    ///
+    /// Fails if the request does not serialize, such as a hand-built [`RerankRequest`]
+    /// with more than 100 documents, so invalid requests never share a key.
+    fn stable_hash(&self) -> Result<u64, VoyageError> {
+        let mut hasher = Fnv1a(FNV_OFFSET);
+        serde_json::to_writer(&mut hasher, self)?;
+        Ok(hasher.0)
+    }
+
+    /// [`stable_hash`](Self::stable_hash) as 16 lowercase hex digits.
+    fn stable_key(&self) -> Result<String, VoyageError> {
+        Ok(format!("{:016x}", self.stable_hash()?))
+    }
+}
+
+impl StableHash for EmbeddingsRequest {}
+
+impl StableHash for RerankRequest {}
+
+/// FNV-1a hash of `bytes`, mixed with `seed`.
+pub(crate) fn fnv1a(seed: u64, bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv1a(FNV_OFFSET ^ seed);
+    hasher.update(bytes);
+    hasher.0
+}
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+impl io::Write for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+/// Headers that may carry the server's id for a request, in order of preference.
+const REQUEST_ID_HEADERS: [&str; 3] = ["x-request-id", "request-id", "x-amzn-requestid"];
+
+/// How a response was obtained, for monitoring and support requests.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseMetadata {
+    /// Time from the call until the response body was received, including rate-limit
+    /// waits and retries.
+    pub latency: Duration,
+    /// Time spent waiting for the client-side rate limiter.
+    pub rate_limit_wait: Duration,
+    /// Number of times the request was retried, across all keys tried.
+    pub retries: u32,
+    /// The server's id for the request, from `x-request-id` or a similar header.
+    pub request_id: Option<String>,
+    /// Rate-limit state reported by the server.
+    pub rate_limit: RateLimitHeaders,
+}
+
+/// The `x-ratelimit-*` response headers. Fields are `None` when the header is absent or
+/// cannot be parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitHeaders {
+    pub limit_requests: Option<u64>,
+    pub limit_tokens: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request budget resets.
+    pub reset_requests: Option<Duration>,
+    /// Time until the token budget resets.
+    pub reset_tokens: Option<Duration>,
+}
+
+impl ResponseMetadata {
+    /// Returns true if nothing has been recorded, as for responses built locally.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fills `request_id` and `rate_limit` from response headers.
+    pub fn read_headers(&mut self, headers: &HeaderMap) {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        self.request_id = REQUEST_ID_HEADERS
+            .iter()
+            .find_map(|name| header(name))
+            .map(str::to_string);
+
+        let count = |name: &str| header(name).and_then(|v| v.trim().parse().ok());
+        let reset = |name: &str| header(name).and_then(parse_reset);
+        self.rate_limit = RateLimitHeaders {
+            limit_requests: count("x-ratelimit-limit-requests"),
+            limit_tokens: count("x-ratelimit-limit-tokens"),
+            remaining_requests: count("x-ratelimit-remaining-requests"),
+            remaining_tokens: count("x-ratelimit-remaining-tokens"),
+            reset_requests: reset("x-ratelimit-reset-requests"),
+            reset_tokens: reset("x-ratelimit-reset-tokens"),
+        };
+    }
+}
+
+/// Parses a reset time given either as seconds (`"12"`, `"0.5"`) or as a duration with
+/// units (`"250ms"`, `"1.5s"`, `"6m0s"`, `"1h2m3s"`).
+fn parse_reset(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|&len| len > 0)?;
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let scale = match &rest[..unit_len] {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return None,
+        };
+        total += number * scale;
+        rest = &rest[unit_len..];
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
@@ -1,10 +1,14 @@
 pub mod embeddings;
+pub mod hashing;
+pub mod metadata;
 pub mod model_type;
 pub mod rerank;
 pub mod search;
 pub mod usage;
 
 pub use embeddings::{EmbeddingModel, EmbeddingsInput, InputType};
+pub use hashing::StableHash;
+pub use metadata::{RateLimitHeaders, ResponseMetadata};
 pub use model_type::ModelType;
 pub use rerank::{RerankModel, RerankRequest, RerankResponse};
 pub use search::{SearchModel, SearchType};
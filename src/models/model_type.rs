@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 use crate::models::{EmbeddingModel, RerankModel};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum ModelType {
     Rerank(RerankModel),
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::metadata::ResponseMetadata;
+use crate::models::usage::estimate_tokens;
+
 const MAX_DOCUMENTS: usize = 100;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RerankResponse {
     #[serde(default)]
     pub object: String,
@@ -10,6 +13,12 @@ pub struct RerankResponse {
     #[serde(default)]
     pub model: String,
     pub usage: Usage,
+    /// Redacted label of the API key that served the request, e.g. `#1 …9f2c`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub served_by: Option<String>,
+    /// Latency, retries, request id and rate-limit headers of the call.
+    #[serde(default, skip_serializing_if = "ResponseMetadata::is_empty")]
+    pub metadata: ResponseMetadata,
 }
 
 /// Represents one of the input documents after reranking, including its relevance score
@@ -18,7 +27,7 @@ pub struct RerankResponse {
 /// The reranking operation takes a list of documents and returns them ordered by
 /// relevance to the query, with scores attached. Each RerankResult corresponds to
 /// one of the input documents.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RerankResult {
     /// Relevance score from 0.0 to 1.0, where higher scores indicate
     /// greater relevance to the query
@@ -29,7 +38,7 @@ pub struct RerankResult {
     /// A copy of the original document text that was scored.
     /// This is the same text that was provided in the input documents array
     /// at position `index`. May be omitted in API responses.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub document: Option<String>,
 }
 
@@ -53,6 +62,14 @@ pub enum RerankModel {
 }
 
 impl RerankModel {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rerank2 => "rerank-2",
+            Self::Rerank2Lite => "rerank-2-lite",
+            Self::RerankLite1 => "rerank-lite-1",
+        }
+    }
+
     pub const fn max_context_length(&self) -> usize {
         match self {
             Self::Rerank2 => 16000,
@@ -68,29 +85,42 @@ impl RerankModel {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Usage {
     pub total_tokens: u32,
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[non_exhaustive]
 pub enum ValidationError {
+    #[error("query cannot be empty")]
+    EmptyQuery,
     #[error("documents cannot be empty")]
     EmptyDocuments,
+    #[error("document {index} is empty")]
+    EmptyDocument { index: usize },
     #[error("documents cannot contain more than {MAX_DOCUMENTS} items")]
     TooManyDocuments,
+    #[error("query plus document {index} is about {tokens} tokens (context length: {limit})")]
+    QueryDocumentTokenLimitExceeded {
+        index: usize,
+        tokens: usize,
+        limit: usize,
+    },
 }
 
 /// Request to rerank a set of documents based on their relevance to a query.
-#[derive(Debug, Serialize)]
+///
+/// Deserializing a request runs the same checks as [`RerankRequest::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedRerankRequest")]
 pub struct RerankRequest {
     /// The query text to compare documents against
     pub query: String,
     /// The collection of documents to be reranked by relevance to the query.
     /// Maximum 100 documents. Each document will appear exactly once in the
     /// response, but ordered by relevance score.
-    #[serde(with = "validate_documents")]
+    #[serde(serialize_with = "validate_documents::serialize")]
     pub documents: Vec<String>,
     /// The reranking model to use
     pub model: RerankModel,
@@ -98,27 +128,101 @@ pub struct RerankRequest {
     /// If set, only returns the top K most relevant documents.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_k: Option<usize>,
+    /// Whether the API may truncate a query and document pair to the model's context
+    /// length. Defaults to `true` on the server when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<bool>,
 }
 
 impl RerankRequest {
+    /// Creates a request, checking that the query and documents are not empty and the
+    /// document count.
+    ///
+    /// The API truncates over-long query and document pairs by default, so their length
+    /// is only checked once truncation is turned off with [`RerankRequest::with_truncation`].
     pub fn new(
         query: String,
         documents: Vec<String>,
         model: RerankModel,
         top_k: Option<usize>,
     ) -> Result<Self, ValidationError> {
-        if documents.is_empty() {
-            return Err(ValidationError::EmptyDocuments);
-        }
-        if documents.len() > MAX_DOCUMENTS {
-            return Err(ValidationError::TooManyDocuments);
-        }
-        Ok(Self {
+        let request = Self {
             query,
             documents,
             model,
             top_k,
-        })
+            truncation: None,
+        };
+        request.validate()?;
+        Ok(request)
+    }
+
+    /// Sets whether the API may truncate inputs. With truncation off, fails if the query
+    /// plus any document exceeds the model's context length (using [`estimate_tokens`]).
+    pub fn with_truncation(mut self, truncation: bool) -> Result<Self, ValidationError> {
+        self.truncation = Some(truncation);
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Runs the checks of [`RerankRequest::new`] and [`RerankRequest::with_truncation`].
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.query.is_empty() {
+            return Err(ValidationError::EmptyQuery);
+        }
+        if self.documents.is_empty() {
+            return Err(ValidationError::EmptyDocuments);
+        }
+        if self.documents.len() > MAX_DOCUMENTS {
+            return Err(ValidationError::TooManyDocuments);
+        }
+        if let Some(index) = self.documents.iter().position(String::is_empty) {
+            return Err(ValidationError::EmptyDocument { index });
+        }
+        if self.truncation != Some(false) {
+            return Ok(());
+        }
+        let query_tokens = estimate_tokens(&self.query);
+        let limit = self.model.max_context_length();
+        for (index, document) in self.documents.iter().enumerate() {
+            let tokens = query_tokens + estimate_tokens(document);
+            if tokens > limit {
+                return Err(ValidationError::QueryDocumentTokenLimitExceeded {
+                    index,
+                    tokens,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The wire form of [`RerankRequest`], validated on conversion.
+#[derive(Deserialize)]
+struct UncheckedRerankRequest {
+    query: String,
+    documents: Vec<String>,
+    model: RerankModel,
+    #[serde(default)]
+    top_k: Option<usize>,
+    #[serde(default)]
+    truncation: Option<bool>,
+}
+
+impl TryFrom<UncheckedRerankRequest> for RerankRequest {
+    type Error = ValidationError;
+
+    fn try_from(request: UncheckedRerankRequest) -> Result<Self, Self::Error> {
+        let request = RerankRequest {
+            query: request.query,
+            documents: request.documents,
+            model: request.model,
+            top_k: request.top_k,
+            truncation: request.truncation,
+        };
+        request.validate()?;
+        Ok(request)
     }
 }
 
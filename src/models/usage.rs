@@ -1,11 +1,19 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Usage {
     pub total_tokens: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EstimatedUsage {
     pub total_tokens: u32,
 }
+
+/// Rough token count for `text`: one token per four bytes, rounded up.
+///
+/// This is the estimate used for rate limiting and request validation. It is not the
+/// model's tokenizer, so limits checked against it are approximate.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
@@ -1,75 +1,263 @@
 use crate::errors::VoyageError;
+use crate::client::embeddings_client::{EmbeddingClient, EmbeddingsClient};
+use crate::client::rerank_client::{DefaultRerankClient, RerankClient};
 use crate::models::{
-    embeddings::{EmbeddingModel, EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse},
-    rerank::{RerankModel, RerankRequest},
+    embeddings::{EmbeddingsInput, EmbeddingsRequest, EmbeddingsResponse, InputType},
+    rerank::{RerankModel, RerankRequest, RerankResult},
 };
 use crate::VoyageAiClient;
 use async_trait::async_trait;
+use std::sync::Arc;
 
-/// Interface for embedding text into vectors
+/// Interface for embedding text into vectors.
+///
+/// Implementors provide [`embed_texts`](Embedder::embed_texts) and describe their model;
+/// the query, document and untyped helpers are built on it.
 #[async_trait]
 pub trait Embedder: Send + Sync {
-    /// Get embeddings for text
-    async fn embed(&self, text: &str) -> Result<Vec<f32>, VoyageError>;
+    /// Name of the embedding model, e.g. `voyage-3`.
+    fn model_name(&self) -> &str;
 
-    /// Get embeddings for multiple texts
-    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, VoyageError>;
+    /// Length of the vectors this embedder returns.
+    fn dimension(&self) -> usize;
+
+    /// Embeds `texts` with the given input type, returning one vector per text in order.
+    async fn embed_texts(
+        &self,
+        texts: &[String],
+        input_type: Option<InputType>,
+    ) -> Result<Vec<Vec<f32>>, VoyageError>;
+
+    /// Embeds a search query, with `input_type` set to `query`.
+    async fn embed_query(&self, query: &str) -> Result<Vec<f32>, VoyageError> {
+        single(
+            self.embed_texts(&[query.to_string()], Some(InputType::Query))
+                .await?,
+        )
+    }
+
+    /// Embeds documents to be searched, with `input_type` set to `document`.
+    async fn embed_documents(&self, documents: &[String]) -> Result<Vec<Vec<f32>>, VoyageError> {
+        self.embed_texts(documents, Some(InputType::Document)).await
+    }
+
+    /// Get embeddings for text, without an input type
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, VoyageError> {
+        single(self.embed_texts(&[text.to_string()], None).await?)
+    }
+
+    /// Get embeddings for multiple texts, without an input type
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, VoyageError> {
+        self.embed_texts(texts, None).await
+    }
+}
+
+/// The only embedding of a one-text batch.
+fn single(embeddings: Vec<Vec<f32>>) -> Result<Vec<f32>, VoyageError> {
+    embeddings
+        .into_iter()
+        .next()
+        .ok_or_else(|| VoyageError::JsonError("No embedding returned".to_string()))
+}
+
+/// Options for a [`Reranker`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RerankOptions {
+    /// Return only the `top_k` most relevant documents.
+    pub top_k: Option<usize>,
+    /// Model to use instead of the reranker's default. Local rerankers ignore it.
+    pub model: Option<RerankModel>,
+    /// Copy each document's text into its result.
+    pub return_documents: bool,
 }
 
-/// Interface for reranking documents
+impl RerankOptions {
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
+    pub fn with_model(mut self, model: RerankModel) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    pub fn with_documents(mut self) -> Self {
+        self.return_documents = true;
+        self
+    }
+}
+
+/// Interface for reranking documents.
+///
+/// Results are ordered by descending `relevance_score`, ties in input order, and each
+/// carries the `index` of its document in the input.
 #[async_trait]
 pub trait Reranker: Send + Sync {
-    /// Rerank documents based on a query
-    async fn rerank(&self, query: &str, documents: Vec<String>) -> Result<Vec<f32>, VoyageError>;
+    /// Scores `documents` against `query`. An empty `documents` gives an empty result.
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>, VoyageError>;
+
+    /// Scores every document with the default options, returned in input order.
+    async fn rerank_scores(
+        &self,
+        query: &str,
+        documents: &[String],
+    ) -> Result<Vec<f64>, VoyageError> {
+        let results = self
+            .rerank(query, documents, &RerankOptions::default())
+            .await?;
+        let mut scores = vec![0.0; documents.len()];
+        for result in results {
+            if let Some(score) = scores.get_mut(result.index) {
+                *score = result.relevance_score;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+/// Sorts `results` most relevant first, applies `top_k` and fills or clears each
+/// result's document as `options` asks.
+pub(crate) fn finish_rerank(
+    mut results: Vec<RerankResult>,
+    documents: &[String],
+    options: &RerankOptions,
+) -> Vec<RerankResult> {
+    results.sort_by(|a, b| {
+        b.relevance_score
+            .total_cmp(&a.relevance_score)
+            .then(a.index.cmp(&b.index))
+    });
+    if let Some(top_k) = options.top_k {
+        results.truncate(top_k);
+    }
+    for result in &mut results {
+        result.document = if options.return_documents {
+            documents.get(result.index).cloned()
+        } else {
+            None
+        };
+    }
+    results
 }
 
 impl VoyageAiClient {
     pub async fn embeddings(&self, request: EmbeddingsRequest) -> Result<EmbeddingsResponse, VoyageError> {
-        self.embeddings_client().create_embedding(&request).await
+        self.config.embeddings.create_embedding(&request).await
+    }
+}
+
+/// Embeds `texts` with `client`'s model, one request for the whole slice.
+async fn embed_with<C: EmbeddingsClient + ?Sized>(
+    client: &C,
+    texts: &[String],
+    input_type: Option<InputType>,
+) -> Result<Vec<Vec<f32>>, VoyageError> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let request = EmbeddingsRequest {
+        input: EmbeddingsInput::Multiple(texts.to_vec()),
+        model: client.embedding_model(),
+        input_type,
+        truncation: None,
+        encoding_format: None,
+    };
+    let response = client.create_embedding(&request).await?;
+    Ok(response.data.into_iter().map(|d| d.embedding).collect())
+}
+
+#[async_trait]
+impl Embedder for EmbeddingClient {
+    fn model_name(&self) -> &str {
+        self.config().embedding_model.as_str()
+    }
+
+    fn dimension(&self) -> usize {
+        self.config().embedding_model.embedding_dimension()
+    }
+
+    async fn embed_texts(
+        &self,
+        texts: &[String],
+        input_type: Option<InputType>,
+    ) -> Result<Vec<Vec<f32>>, VoyageError> {
+        embed_with(self, texts, input_type).await
+    }
+}
+
+/// Any shared [`EmbeddingsClient`], including `Arc<dyn EmbeddingsClient>`, is an
+/// [`Embedder`] for its model.
+#[async_trait]
+impl<C: EmbeddingsClient + ?Sized> Embedder for Arc<C> {
+    fn model_name(&self) -> &str {
+        self.embedding_model_name()
+    }
+
+    fn dimension(&self) -> usize {
+        self.as_ref().embedding_dimension()
+    }
+
+    async fn embed_texts(
+        &self,
+        texts: &[String],
+        input_type: Option<InputType>,
+    ) -> Result<Vec<Vec<f32>>, VoyageError> {
+        embed_with(self.as_ref(), texts, input_type).await
     }
 }
 
 #[async_trait]
 impl Embedder for VoyageAiClient {
-    async fn embed(&self, text: &str) -> Result<Vec<f32>, VoyageError> {
-        let request = EmbeddingsRequest {
-            input: EmbeddingsInput::Single(text.to_string()),
-            model: EmbeddingModel::Voyage3,
-            input_type: None,
-            truncation: None,
-            encoding_format: None,
-        };
+    fn model_name(&self) -> &str {
+        Embedder::model_name(&self.config.embeddings)
+    }
 
-        let embeddings = self.embeddings(request).await?;
-        Ok(embeddings.data[0].embedding.clone())
+    fn dimension(&self) -> usize {
+        self.config.embeddings.dimension()
     }
 
-    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, VoyageError> {
-        let request = EmbeddingsRequest {
-            input: EmbeddingsInput::Multiple(texts.to_vec()),
-            model: EmbeddingModel::Voyage3,
-            input_type: None,
-            truncation: None,
-            encoding_format: None,
-        };
+    async fn embed_texts(
+        &self,
+        texts: &[String],
+        input_type: Option<InputType>,
+    ) -> Result<Vec<Vec<f32>>, VoyageError> {
+        self.config.embeddings.embed_texts(texts, input_type).await
+    }
+}
 
-        let embeddings = self.embeddings(request).await?;
-        Ok(embeddings.data.into_iter().map(|d| d.embedding).collect())
+#[async_trait]
+impl Reranker for DefaultRerankClient {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>, VoyageError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        let model = options.model.unwrap_or(self.config().rerank_model);
+        let request =
+            RerankRequest::new(query.to_string(), documents.to_vec(), model, options.top_k)?;
+        let response = RerankClient::rerank(self, &request).await?;
+        Ok(finish_rerank(response.data, documents, options))
     }
 }
+
 #[async_trait]
 impl Reranker for VoyageAiClient {
-    async fn rerank(&self, query: &str, documents: Vec<String>) -> Result<Vec<f32>, VoyageError> {
-        let request = RerankRequest::new(query.to_string(), documents, RerankModel::Rerank2, None)
-            .map_err(VoyageError::from)?;
-        let response = self.rerank(request).await.map_err(|e| {
-            VoyageError::ApiError(reqwest::StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
-        })?;
-        let scores: Vec<f32> = response
-            .data
-            .into_iter()
-            .map(|r| r.relevance_score as f32)
-            .collect();
-        Ok(scores)
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        options: &RerankOptions,
+    ) -> Result<Vec<RerankResult>, VoyageError> {
+        Reranker::rerank(self.config.rerank_client.as_ref(), query, documents, options).await
     }
 }
@@ -0,0 +1,26 @@
+//! Client factories shared by the test suites. Each suite uses only some of them.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::RateLimiter;
+use voyageai::{EmbeddingModel, VoyageConfig};
+
+/// Config pointing at `server` with the key `test_key` and `voyage-3-lite`.
+pub fn config(server: &MockServer) -> VoyageConfig {
+    server
+        .config("test_key")
+        .with_embedding_model(EmbeddingModel::Voyage3Lite)
+}
+
+/// HTTP embeddings client for `server`; see [`config`].
+pub fn client(server: &MockServer) -> EmbeddingClient {
+    client_with_config(config(server))
+}
+
+/// HTTP embeddings client for `config`, with a rate limiter of its own.
+pub fn client_with_config(config: VoyageConfig) -> EmbeddingClient {
+    EmbeddingClient::new(config, Arc::new(RateLimiter::new())).expect("valid client config")
+}
@@ -3,7 +3,6 @@ use log::{debug, info, LevelFilter};
 use voyageai::{
     client::SearchRequest,
     models::search::{SearchModel, SearchQuery, SearchType},
-    traits::voyage::VoyageAiClientExt,
     EmbeddingModel, EmbeddingsInput, EmbeddingsRequestBuilder, InputType, RerankModel,
     RerankRequest, VoyageBuilder,
 };
@@ -24,7 +23,7 @@ async fn test_voyage_ai_client() -> Result<(), Box<dyn std::error::Error>> {
         .init();
 
     // Test embeddings
-    let texts = vec![
+    let texts = [
         "Paris is the capital of France",
         "London is the capital of England",
         "Berlin is the capital of Germany",
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use voyageai::backends::{EmbeddingBackend, OpenAiCompatibleBackend, OpenAiCompatibleConfig};
+use voyageai::builder::search::SearchRequestBuilder;
+use voyageai::client::cassette::{Cassette, CassetteMode};
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::{CacheConfig, EmbeddingsClient};
+use voyageai::config::ConfigLoader;
+use voyageai::models::embeddings::{EmbeddingsInput, EmbeddingsRequest};
+use voyageai::traits::llm::Embedder;
+use voyageai::{SearchModel, SearchType, VoyageBuilder, VoyageConfig, VoyageError};
+
+const MODEL: &str = "text-embedding-3-small";
+
+async fn start_server() -> Result<MockServer, VoyageError> {
+    let server = MockServer::start().await?;
+    server.serve_model(MODEL, 64);
+    Ok(server)
+}
+
+fn backend_config(server: &MockServer) -> OpenAiCompatibleConfig {
+    OpenAiCompatibleConfig::new(server.base_url(), MODEL, 64).with_api_key("sk-test")
+}
+
+#[tokio::test]
+async fn test_requests_use_openai_wire_format() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    server.expect_api_key("sk-test");
+    let backend = OpenAiCompatibleBackend::new(backend_config(&server))?;
+
+    let request = EmbeddingsRequest {
+        input: EmbeddingsInput::Multiple(vec!["alpha".to_string(), "beta".to_string()]),
+        model: backend.embedding_model(),
+        input_type: None,
+        truncation: None,
+        encoding_format: None,
+    };
+    let response = backend.create_embedding(&request).await?;
+
+    assert_eq!(response.model, MODEL);
+    assert_eq!(response.data.len(), 2);
+    assert!(response.data.iter().all(|d| d.embedding.len() == 64));
+    assert!(response.usage.total_tokens > 0);
+    let requests = server.requests();
+    let body = requests[0].json().unwrap();
+    assert_eq!(requests[0].path, "/v1/embeddings");
+    assert_eq!(body["model"], MODEL);
+    assert_eq!(body["encoding_format"], "float");
+    assert!(body.get("input_type").is_none());
+    assert!(body.get("dimensions").is_none());
+    assert_eq!(backend.metrics().snapshot().requests, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_model_metadata_and_prefixes() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    let backend: Arc<dyn EmbeddingsClient> = Arc::new(OpenAiCompatibleBackend::new(
+        backend_config(&server).with_prefixes("query: ", "passage: "),
+    )?);
+
+    assert_eq!(backend.model_name(), MODEL);
+    assert_eq!(backend.dimension(), 64);
+    let query = backend.embed_query("what is rust?").await?;
+    backend
+        .embed_documents(&["Rust is a language".to_string()])
+        .await?;
+    Embedder::embed(&backend, "plain").await?;
+
+    assert_eq!(query.len(), 64);
+    let inputs: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|r| r.json().unwrap()["input"][0].clone())
+        .collect();
+    assert_eq!(
+        inputs,
+        [
+            "query: what is rust?",
+            "passage: Rust is a language",
+            "plain"
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_dimensions_and_batching() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    let backend = Arc::new(OpenAiCompatibleBackend::new(
+        OpenAiCompatibleConfig::new(server.base_url(), MODEL, 16)
+            .with_send_dimensions(true)
+            .with_max_batch_size(2),
+    )?);
+    let texts: Vec<String> = (0..5).map(|i| format!("text {}", i)).collect();
+
+    let embeddings = backend.embed_documents(&texts).await?;
+
+    assert_eq!(embeddings.len(), 5);
+    assert!(embeddings.iter().all(|e| e.len() == 16));
+    let bodies: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|r| r.json().unwrap())
+        .collect();
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(bodies[0]["dimensions"], 16);
+    assert_eq!(bodies[2]["input"], serde_json::json!(["text 4"]));
+    let again = backend.embed_documents(&texts[4..]).await?;
+    assert_eq!(again[0], embeddings[4]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_server_errors_are_reported() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    server.expect_api_key("another-key");
+    let backend = OpenAiCompatibleBackend::new(backend_config(&server))?;
+
+    let result = Arc::new(backend).embed_query("rust").await;
+
+    assert!(matches!(result, Err(VoyageError::Unauthorized)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_builder_selects_backend() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("unused")
+        .with_embedding_backend(EmbeddingBackend::OpenAiCompatible(backend_config(&server)))
+        .build()?;
+
+    assert_eq!(Embedder::model_name(&client), MODEL);
+    assert_eq!(Embedder::dimension(&client), 64);
+    let batch = client
+        .embed_batch(vec!["rust".to_string(), "go".to_string()])
+        .await?;
+    assert_eq!(batch.len(), 2);
+
+    let request = SearchRequestBuilder::new()
+        .query("rust")
+        .documents(["go", "rust"].map(String::from))
+        .model(SearchModel::default())
+        .search_type(SearchType::Similarity)
+        .build()
+        .expect("search request");
+    let results = client.search(request).await?;
+    assert_eq!(results.len(), 2);
+    assert!(server
+        .requests()
+        .iter()
+        .all(|r| r.json().unwrap()["model"] == MODEL));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_voyage_client_features_are_not_inherited() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    let path = std::env::temp_dir().join(format!(
+        "voyageai-backend-cassette-{}.json",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let base = VoyageConfig::new("voyage-key")
+        .with_cassette(Cassette::open(&path, CassetteMode::Record)?)
+        .with_dedupe_inputs(true);
+    let backend = OpenAiCompatibleBackend::with_settings_from(backend_config(&server), &base)?;
+
+    let embeddings = Arc::new(backend)
+        .embed_documents(&["rust".to_string(), "rust".to_string()])
+        .await?;
+
+    assert_eq!(embeddings.len(), 2);
+    assert!(!path.exists(), "backend calls must not be recorded");
+    let body = server.requests()[0].json().unwrap();
+    assert_eq!(body["input"], serde_json::json!(["rust", "rust"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_builder_caches_backend_responses() -> Result<(), VoyageError> {
+    let server = start_server().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("unused")
+        .with_embedding_backend(EmbeddingBackend::OpenAiCompatible(backend_config(&server)))
+        .with_cache(CacheConfig::new(8))
+        .build()?;
+
+    let first = client.embed_text("rust").await?;
+    let second = client.embed_text("rust").await?;
+
+    assert_eq!(first, second);
+    assert_eq!(server.requests().len(), 1);
+    let stats = client.cache().expect("cache configured").stats();
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::BlockingClient;
+use voyageai::{RerankModel, RerankRequest, VoyageError};
+
+/// Starts a mock server on its own runtime, which must outlive the server.
+fn start_server() -> (tokio::runtime::Runtime, MockServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(MockServer::start()).unwrap();
+    (runtime, server)
+}
+
+#[test]
+fn test_blocking_calls_from_plain_thread() -> Result<(), VoyageError> {
+    let (_runtime, server) = start_server();
+    let client = BlockingClient::new_with_config(server.config("test_key"))?;
+
+    let batch = client.embed_batch(vec!["one".to_string(), "two".to_string()])?;
+    assert_eq!(batch.len(), 2);
+    assert_eq!(client.embed_text("one")?, batch[0]);
+    let response = client.embed("one").expect("embed succeeds");
+    assert_eq!(response.data[0].embedding, batch[0]);
+
+    let request = RerankRequest::new(
+        "rust".to_string(),
+        vec!["python code".to_string(), "rust code".to_string()],
+        RerankModel::Rerank2,
+        None,
+    )?;
+    let reranked = client.rerank(request)?;
+    assert_eq!(reranked.data.len(), 2);
+    assert_eq!(server.requests().len(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_blocking_client_is_shared_across_threads() -> Result<(), VoyageError> {
+    let (_runtime, server) = start_server();
+    let client = BlockingClient::new_with_config(server.config("test_key"))?;
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let client = client.clone();
+            std::thread::spawn(move || client.embed_text(format!("text {}", i)))
+        })
+        .collect();
+    for handle in handles {
+        assert!(!handle.join().unwrap()?.is_empty());
+    }
+    Ok(())
+}
+
+// The mock server runs on this test's runtime, so it needs a second worker thread while
+// the blocking call holds the first.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_blocking_calls_inside_runtime_do_not_panic() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = BlockingClient::new_with_config(server.config("test_key"))?;
+
+    let embedding = client.embed_text("inside a runtime")?;
+    assert!(!embedding.is_empty());
+
+    // Dropping the last clone inside async code must not panic either.
+    drop(client);
+    Ok(())
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use common::client;
+use serde_json::Value;
+use voyageai::bulk::{BulkJob, BulkProgress, Checkpoint};
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::{EmbeddingModel, VoyageError};
+
+fn workdir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("voyageai-bulk-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn jsonl(count: usize) -> String {
+    (0..count)
+        .map(|i| {
+            format!(
+                "{{\"id\": \"doc-{}\", \"text\": \"text number {}\"}}\n",
+                i, i
+            )
+        })
+        .collect()
+}
+
+fn output_lines(path: &PathBuf) -> Vec<Value> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_bulk_jsonl_writes_embeddings_in_order() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let dir = workdir("jsonl");
+    let input = dir.join("input.jsonl");
+    let output = dir.join("output.jsonl");
+    fs::write(&input, jsonl(10)).unwrap();
+
+    let report = BulkJob::new(client(&server), &input, &output)
+        .with_batch_size(4)
+        .run()
+        .await?;
+
+    assert_eq!(
+        (report.records, report.batches, report.resumed_from),
+        (10, 3, 0)
+    );
+    assert!(report.tokens > 0);
+    let lines = output_lines(&output);
+    let ids: Vec<&str> = lines.iter().map(|l| l["id"].as_str().unwrap()).collect();
+    let expected: Vec<String> = (0..10).map(|i| format!("doc-{}", i)).collect();
+    assert_eq!(ids, expected);
+    assert_eq!(lines[0]["embedding"].as_array().unwrap().len(), 512);
+
+    let checkpoint = Checkpoint::load(&dir.join("output.jsonl.checkpoint.json"))?.unwrap();
+    assert_eq!(checkpoint.records_done, 10);
+    assert_eq!(
+        checkpoint.output_bytes,
+        fs::metadata(&output).unwrap().len()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_csv_with_custom_fields() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let dir = workdir("csv");
+    let input = dir.join("input.csv");
+    let output = dir.join("output.jsonl");
+    fs::write(&input, "key,body\na,\"first, with comma\"\nb,second\n").unwrap();
+
+    let report = BulkJob::new(client(&server), &input, &output)
+        .with_id_field("key")
+        .with_text_field("body")
+        .run()
+        .await?;
+
+    assert_eq!(report.records, 2);
+    let sent = server.requests()[0].json().unwrap();
+    assert_eq!(sent["input"][0], "first, with comma");
+    let ids: Vec<Value> = output_lines(&output)
+        .into_iter()
+        .map(|l| l["id"].clone())
+        .collect();
+    assert_eq!(ids, ["a", "b"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_resumes_after_failure() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let dir = workdir("resume");
+    let input = dir.join("input.jsonl");
+    let output = dir.join("output.jsonl");
+    let mut records = jsonl(6);
+    records = records.replacen("{\"id\": \"doc-4\"", "{broken", 1);
+    fs::write(&input, &records).unwrap();
+    let job = BulkJob::new(client(&server), &input, &output)
+        .with_batch_size(2)
+        .with_concurrency(1);
+
+    let error = job.run().await.unwrap_err();
+    assert!(matches!(error, VoyageError::BulkJobError(ref m) if m.contains("line 5")));
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(output_lines(&output).len(), 4);
+
+    // Simulate a crash halfway through writing the next batch.
+    fs::OpenOptions::new()
+        .append(true)
+        .open(&output)
+        .unwrap()
+        .write_all(b"{\"id\": \"partial")
+        .unwrap();
+    fs::write(&input, jsonl(6)).unwrap();
+
+    let report = job.run().await?;
+    assert_eq!(
+        (report.resumed_from, report.records, report.batches),
+        (4, 6, 3)
+    );
+    assert_eq!(server.requests().len(), 3);
+    let sent = server.requests()[2].json().unwrap();
+    assert_eq!(
+        sent["input"],
+        serde_json::json!(["text number 4", "text number 5"])
+    );
+    let ids: Vec<Value> = output_lines(&output)
+        .into_iter()
+        .map(|l| l["id"].clone())
+        .collect();
+    assert_eq!(ids, ["doc-0", "doc-1", "doc-2", "doc-3", "doc-4", "doc-5"]);
+
+    let again = job.run().await?;
+    assert_eq!((again.resumed_from, again.records), (6, 6));
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_resume_checks_input_and_model() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let dir = workdir("resume-checks");
+    let input = dir.join("input.jsonl");
+    let other = dir.join("other.jsonl");
+    let output = dir.join("output.jsonl");
+    fs::write(&input, jsonl(3)).unwrap();
+    fs::write(&other, jsonl(3)).unwrap();
+    BulkJob::new(client(&server), &input, &output).run().await?;
+
+    let result = BulkJob::new(client(&server), &other, &output).run().await;
+    assert!(matches!(result, Err(VoyageError::BulkJobError(ref m)) if m.contains("belongs to")));
+    let result = BulkJob::new(client(&server), &input, &output)
+        .with_model(EmbeddingModel::Voyage3)
+        .run()
+        .await;
+    assert!(matches!(result, Err(VoyageError::BulkJobError(ref m)) if m.contains("voyage-3-lite")));
+    assert_eq!(server.requests().len(), 1);
+
+    // The same file under another spelling resumes.
+    let report = BulkJob::new(client(&server), dir.join(".").join("input.jsonl"), &output)
+        .run()
+        .await?;
+    assert_eq!((report.resumed_from, report.records), (3, 3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_api_error_keeps_checkpoint() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.enqueue(MockResponse::status(400));
+    let dir = workdir("api-error");
+    let input = dir.join("input.jsonl");
+    let output = dir.join("output.jsonl");
+    fs::write(&input, jsonl(3)).unwrap();
+
+    let job = BulkJob::new(client(&server), &input, &output);
+    assert!(matches!(
+        job.run().await,
+        Err(VoyageError::BadRequest { .. })
+    ));
+    assert!(output_lines(&output).is_empty());
+
+    assert_eq!(job.run().await?.records, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_reports_progress() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let dir = workdir("progress");
+    let input = dir.join("input.jsonl");
+    let output = dir.join("output.jsonl");
+    fs::write(&input, jsonl(5)).unwrap();
+
+    // Give the background count time to finish before the batches do.
+    server.set_latency(std::time::Duration::from_millis(50));
+    let seen: Arc<Mutex<Vec<BulkProgress>>> = Arc::default();
+    let sink = seen.clone();
+    BulkJob::new(client(&server), &input, &output)
+        .with_batch_size(2)
+        .with_progress(move |p| sink.lock().unwrap().push(*p))
+        .run()
+        .await?;
+
+    let seen = seen.lock().unwrap();
+    let done: Vec<u64> = seen.iter().map(|p| p.records_done).collect();
+    assert_eq!(done, [2, 4, 5]);
+    assert!(seen.iter().all(|p| p.records_total.is_none_or(|t| t == 5)));
+    assert_eq!(seen.last().unwrap().records_total, Some(5));
+    assert!(seen.windows(2).all(|w| w[0].tokens < w[1].tokens));
+    assert_eq!(seen.last().unwrap().eta, Some(std::time::Duration::ZERO));
+    assert!(seen[0].throughput > 0.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_rejects_checkpoint_of_other_input() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let dir = workdir("mismatch");
+    let output = dir.join("output.jsonl");
+    fs::write(dir.join("a.jsonl"), jsonl(1)).unwrap();
+    fs::write(dir.join("b.jsonl"), jsonl(1)).unwrap();
+
+    BulkJob::new(client(&server), dir.join("a.jsonl"), &output)
+        .run()
+        .await?;
+    let error = BulkJob::new(client(&server), dir.join("b.jsonl"), &output)
+        .run()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, VoyageError::BulkJobError(_)));
+    Ok(())
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use std::time::Duration;
+
+use common::client;
+use voyageai::client::cache::{CacheConfig, CacheStats, CachedEmbeddingsClient};
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::EmbeddingsClient;
+use voyageai::config::ConfigLoader;
+use voyageai::models::embeddings::EmbeddingsRequest;
+use voyageai::{EmbeddingModel, EmbeddingsRequestBuilder, InputType, VoyageBuilder, VoyageError};
+
+fn cached_client(
+    server: &MockServer,
+    cache: CacheConfig,
+) -> CachedEmbeddingsClient<EmbeddingClient> {
+    CachedEmbeddingsClient::new(client(server), cache)
+}
+
+fn request(texts: &[&str]) -> EmbeddingsRequest {
+    EmbeddingsRequestBuilder::new()
+        .documents(texts.iter().copied())
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_repeated_requests_are_served_from_cache() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = cached_client(&server, CacheConfig::default());
+
+    let first = client.create_embedding(&request(&["a", "b"])).await?;
+    let second = client
+        .clone()
+        .create_embedding(&request(&["a", "b"]))
+        .await?;
+    let query = EmbeddingsRequestBuilder::new()
+        .documents(["a", "b"])
+        .model(EmbeddingModel::Voyage3Lite)
+        .input_type(InputType::Query)
+        .build()?;
+    client.create_embedding(&query).await?;
+
+    assert_eq!(first.data, second.data);
+    assert_eq!(server.requests().len(), 2);
+    let cache = client.cache();
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 2,
+            entries: 2
+        }
+    );
+    cache.clear();
+    client.create_embedding(&request(&["a", "b"])).await?;
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_least_recently_used_entry_is_evicted() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = cached_client(&server, CacheConfig::new(2));
+
+    for texts in [&["a"], &["b"], &["a"], &["c"], &["a"], &["b"]] {
+        client.create_embedding(&request(texts)).await?;
+    }
+
+    // "b" was evicted when "c" arrived, since "a" had been used more recently.
+    let sent: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|r| r.json().unwrap()["input"][0].clone())
+        .collect();
+    assert_eq!(sent, ["a", "b", "c", "b"]);
+    assert_eq!(client.cache().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_expired_entries_are_refetched() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = cached_client(
+        &server,
+        CacheConfig::default().with_ttl(Duration::from_millis(50)),
+    );
+
+    client.create_embedding(&request(&["a"])).await?;
+    client.create_embedding(&request(&["a"])).await?;
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    client.create_embedding(&request(&["a"])).await?;
+
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_errors_are_not_cached() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.expect_api_key("another_key");
+    let client = cached_client(&server, CacheConfig::default());
+
+    assert!(client.create_embedding(&request(&["a"])).await.is_err());
+
+    assert!(client.cache().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_builder_enables_cache() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("test_key")
+        .with_base_url(server.base_url())
+        .with_cache(CacheConfig::new(8))
+        .build()?;
+
+    client.embed_batch(vec!["a".to_string()]).await?;
+    client.embed_batch(vec!["a".to_string()]).await?;
+
+    assert_eq!(server.requests().len(), 1);
+    let cache = client.cache().expect("cache configured");
+    assert_eq!(cache.config().capacity, 8);
+    Ok(())
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::client_with_config;
+use voyageai::client::cassette::{Cassette, CassetteMode, REDACTED};
+use voyageai::client::mock_server::MockServer;
+use voyageai::{VoyageConfig, VoyageError};
+
+const API_KEY: &str = "pa-secret-test-key";
+
+fn cassette_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("voyageai-cassettes-{}", std::process::id()))
+        .join(format!("{}.json", name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn test_record_then_replay_without_network() -> Result<(), VoyageError> {
+    let path = cassette_path("record_then_replay");
+    let server = MockServer::start().await?;
+
+    let recorder = Cassette::open(&path, CassetteMode::Record)?;
+    let recorded = client_with_config(server.config(API_KEY).with_cassette(recorder))
+        .embed("hello cassette")
+        .await?;
+    let base_url = server.base_url();
+    drop(server);
+
+    let player = Cassette::open(&path, CassetteMode::ReplayOnly)?;
+    let config = VoyageConfig::new(API_KEY.to_string())
+        .with_base_url(base_url)
+        .with_cassette(player);
+    let replayed = client_with_config(config).embed("hello cassette").await?;
+
+    assert_eq!(recorded, replayed);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replay_only_fails_on_miss() -> Result<(), VoyageError> {
+    let path = cassette_path("replay_miss");
+    let server = MockServer::start().await?;
+    let recorder = Cassette::open(&path, CassetteMode::Record)?;
+    client_with_config(server.config(API_KEY).with_cassette(recorder))
+        .embed("recorded")
+        .await?;
+
+    let player = Cassette::open(&path, CassetteMode::ReplayOnly)?;
+    let result = client_with_config(server.config(API_KEY).with_cassette(player))
+        .embed("never recorded")
+        .await;
+
+    assert!(matches!(result, Err(VoyageError::CassetteMiss(_))));
+    assert_eq!(server.requests().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replay_only_requires_existing_cassette() {
+    let path = cassette_path("missing");
+
+    let result = Cassette::open(&path, CassetteMode::ReplayOnly);
+
+    assert!(matches!(result, Err(VoyageError::CassetteMiss(_))));
+}
+
+#[tokio::test]
+async fn test_passthrough_records_nothing() -> Result<(), VoyageError> {
+    let path = cassette_path("passthrough");
+    let server = MockServer::start().await?;
+    let cassette = Cassette::open(&path, CassetteMode::Passthrough)?;
+
+    client_with_config(server.config(API_KEY).with_cassette(cassette.clone()))
+        .embed("not recorded")
+        .await?;
+
+    assert!(cassette.interactions().is_empty());
+    assert!(!path.exists());
+    assert_eq!(server.requests().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cassette_file_never_contains_api_key() -> Result<(), VoyageError> {
+    let path = cassette_path("redaction");
+    let server = MockServer::start().await?;
+    let cassette = Cassette::open(&path, CassetteMode::Record)?;
+
+    client_with_config(server.config(API_KEY).with_cassette(cassette.clone()))
+        .embed(format!("text mentioning {}", API_KEY).as_str())
+        .await?;
+
+    let contents = std::fs::read_to_string(&path)?;
+    assert!(!contents.contains(API_KEY));
+    assert!(contents.contains(REDACTED));
+
+    let interaction = &cassette.interactions()[0];
+    assert_eq!(interaction.request.method, "POST");
+    assert_eq!(interaction.request.path, "/embeddings");
+    Ok(())
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::client_with_config;
+use voyageai::client::circuit_breaker::CircuitBreaker;
+use voyageai::client::client_limiter::Endpoint;
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::client::{CircuitBreakerConfig, CircuitState, CircuitTransition};
+use voyageai::config::ConfigLoader;
+use voyageai::{
+    EmbeddingModel, EmbeddingsRequestBuilder, RerankModel, RerankRequest, VoyageBuilder,
+    VoyageError,
+};
+
+const BREAKER: CircuitBreakerConfig = CircuitBreakerConfig {
+    failure_threshold: 3,
+    open_duration: Duration::from_millis(100),
+    half_open_probes: 1,
+};
+
+type Transitions = Arc<Mutex<Vec<(CircuitState, CircuitState)>>>;
+
+fn client(server: &MockServer) -> (EmbeddingClient, Transitions) {
+    let transitions = Transitions::default();
+    let seen = transitions.clone();
+    let config = server
+        .config("test_key")
+        .with_circuit_breaker(BREAKER)
+        .with_circuit_listener(move |t: &CircuitTransition| {
+            seen.lock().unwrap().push((t.from, t.to));
+        });
+    (client_with_config(config), transitions)
+}
+
+async fn embed(client: &EmbeddingClient) -> Result<(), VoyageError> {
+    let request = EmbeddingsRequestBuilder::new()
+        .input("hello")
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    client.create_embedding(&request).await.map(|_| ())
+}
+
+fn fail_next(server: &MockServer, status: u16, count: usize) {
+    for _ in 0..count {
+        server.enqueue(MockResponse::status(status));
+    }
+}
+
+#[tokio::test]
+async fn test_circuit_opens_and_fails_fast() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    fail_next(&server, 503, 3);
+    let (client, transitions) = client(&server);
+
+    for _ in 0..3 {
+        assert!(embed(&client).await.is_err());
+    }
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    let result = embed(&client).await;
+    assert!(matches!(
+        result,
+        Err(VoyageError::CircuitOpen { ref endpoint, .. }) if endpoint == "embeddings"
+    ));
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(
+        *transitions.lock().unwrap(),
+        [(CircuitState::Closed, CircuitState::Open)]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_successful_probe_closes_circuit() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    fail_next(&server, 500, 3);
+    let (client, transitions) = client(&server);
+    for _ in 0..3 {
+        let _ = embed(&client).await;
+    }
+
+    tokio::time::sleep(BREAKER.open_duration).await;
+    embed(&client).await?;
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert_eq!(
+        *transitions.lock().unwrap(),
+        [
+            (CircuitState::Closed, CircuitState::Open),
+            (CircuitState::Open, CircuitState::HalfOpen),
+            (CircuitState::HalfOpen, CircuitState::Closed),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_probe_reopens_circuit() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    fail_next(&server, 502, 4);
+    let (client, _) = client(&server);
+    for _ in 0..3 {
+        let _ = embed(&client).await;
+    }
+
+    tokio::time::sleep(BREAKER.open_duration).await;
+    assert!(embed(&client).await.is_err());
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    assert!(matches!(
+        embed(&client).await,
+        Err(VoyageError::CircuitOpen { .. })
+    ));
+    assert_eq!(server.requests().len(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_errors_do_not_trip_circuit() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    fail_next(&server, 400, 5);
+    let (client, transitions) = client(&server);
+
+    for _ in 0..5 {
+        assert!(matches!(
+            embed(&client).await,
+            Err(VoyageError::BadRequest { .. })
+        ));
+    }
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+    assert!(transitions.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_endpoints_have_separate_circuits() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    fail_next(&server, 503, 3);
+    let opened = Arc::new(Mutex::new(Vec::new()));
+    let seen = opened.clone();
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("test_key")
+        .with_base_url(server.base_url())
+        .with_circuit_breaker(BREAKER)
+        .with_circuit_listener(move |t| seen.lock().unwrap().push(t.endpoint))
+        .build()?;
+
+    for _ in 0..3 {
+        assert!(client.embed_text("hello").await.is_err());
+    }
+    let request = RerankRequest::new(
+        "query".to_string(),
+        vec!["a document".to_string()],
+        RerankModel::Rerank2Lite,
+        None,
+    )?;
+    client.rerank(request).await?;
+
+    assert_eq!(*opened.lock().unwrap(), [Endpoint::Embeddings]);
+    assert_eq!(
+        client.config.rerank_client.circuit_state(),
+        Some(CircuitState::Closed)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_half_open_admits_limited_probes() {
+    let breaker = CircuitBreaker::new(Endpoint::Rerank, BREAKER, Vec::new());
+    for _ in 0..3 {
+        breaker.acquire().expect("closed circuit").record(false);
+    }
+    assert!(breaker.acquire().is_err());
+
+    std::thread::sleep(BREAKER.open_duration);
+    let probe = breaker.acquire().expect("first probe");
+    assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    assert!(matches!(
+        breaker.acquire(),
+        Err(VoyageError::CircuitOpen { .. })
+    ));
+
+    drop(probe);
+    breaker.acquire().expect("probe slot freed").record(true);
+    assert_eq!(breaker.state(), CircuitState::Closed);
+}
+
+#[test]
+fn test_circuit_settings_from_env() -> Result<(), VoyageError> {
+    let config = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY", "key")])
+        .load()?;
+    assert_eq!(config.circuit_breaker, None);
+
+    let config = ConfigLoader::new()
+        .with_env_vars([
+            ("VOYAGE_API_KEY", "key"),
+            ("VOYAGE_CIRCUIT_FAILURE_THRESHOLD", "10"),
+            ("VOYAGE_CIRCUIT_OPEN_SECS", "5"),
+        ])
+        .load()?;
+    assert_eq!(
+        config.circuit_breaker,
+        Some(CircuitBreakerConfig {
+            failure_threshold: 10,
+            open_duration: Duration::from_secs(5),
+            half_open_probes: 1,
+        })
+    );
+    Ok(())
+}
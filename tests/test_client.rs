@@ -9,7 +9,7 @@ async fn test_embeddings() -> Result<(), Box<dyn Error>> {
     let config = VoyageConfig::new(
         std::env::var("VOYAGE_API_KEY").unwrap_or_else(|_| "test_key".to_string()),
     );
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     let embeddings_request = EmbeddingsRequestBuilder::new()
         .document("test input")
@@ -30,7 +30,7 @@ async fn test_embeddings() -> Result<(), Box<dyn Error>> {
 #[tokio::test]
 async fn test_invalid_api_key() -> Result<(), Box<dyn Error>> {
     let config = VoyageConfig::new("invalid_api_key".to_string());
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     let embeddings_request = EmbeddingsRequestBuilder::new()
         .document("test input")
@@ -50,7 +50,7 @@ async fn test_reranking() -> Result<(), Box<dyn Error>> {
     let config = VoyageConfig::new(
         std::env::var("VOYAGE_API_KEY").unwrap_or_else(|_| "test_key".to_string()),
     );
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     let rerank_request = RerankRequest::new(
         "test query".to_string(),
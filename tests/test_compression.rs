@@ -0,0 +1,135 @@
+mod common;
+
+use common::client_with_config;
+use voyageai::client::compression::{gunzip, gzip};
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::CompressionConfig;
+use voyageai::config::ConfigLoader;
+use voyageai::{
+    EmbeddingModel, EmbeddingsRequestBuilder, RerankModel, RerankRequest, VoyageBuilder,
+    VoyageError,
+};
+
+fn long_documents(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| format!("Document {} talks about quarterly revenue and growth. ", i).repeat(20))
+        .collect()
+}
+
+fn client(server: &MockServer, compression: CompressionConfig) -> EmbeddingClient {
+    client_with_config(server.config("test_key").with_compression(compression))
+}
+
+#[tokio::test]
+async fn test_large_batches_are_gzipped_both_ways() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server, CompressionConfig::gzip(4 * 1024));
+
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(long_documents(32))
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    let response = client.create_embedding(&request).await?;
+    assert_eq!(response.data.len(), 32);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("content-encoding"), Some("gzip"));
+    assert_eq!(requests[0].header("accept-encoding"), Some("gzip"));
+    assert_eq!(requests[0].json()?["input"][31], long_documents(32)[31]);
+
+    let metrics = client.metrics().snapshot();
+    assert_eq!(metrics.requests, 1);
+    assert_eq!(metrics.compressed_requests, 1);
+    assert_eq!(
+        metrics.uncompressed_bytes_sent,
+        requests[0].body.len() as u64
+    );
+    assert!(metrics.bytes_sent * 4 < metrics.uncompressed_bytes_sent);
+    assert!(metrics.bytes_received < metrics.uncompressed_bytes_received);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_small_bodies_stay_uncompressed() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server, CompressionConfig::gzip(4 * 1024));
+
+    let request = EmbeddingsRequestBuilder::new()
+        .input("short")
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    client.create_embedding(&request).await?;
+
+    assert_eq!(server.requests()[0].header("content-encoding"), None);
+    let metrics = client.metrics().snapshot();
+    assert_eq!(metrics.compressed_requests, 0);
+    assert_eq!(metrics.bytes_sent, metrics.uncompressed_bytes_sent);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compression_is_off_by_default() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("test_key")
+        .with_base_url(server.base_url())
+        .build()?;
+
+    client.embed_batch(long_documents(8)).await?;
+    let rerank = RerankRequest::new(
+        "revenue".to_string(),
+        long_documents(4),
+        RerankModel::Rerank2Lite,
+        None,
+    )?;
+    client.rerank(rerank).await?;
+
+    for request in server.requests() {
+        assert_eq!(request.header("content-encoding"), None);
+        assert_eq!(request.header("accept-encoding"), None);
+    }
+    let metrics = client.metrics().snapshot();
+    assert_eq!(metrics.requests, 2);
+    assert_eq!(metrics.bytes_sent, metrics.uncompressed_bytes_sent);
+    assert_eq!(metrics.bytes_received, metrics.uncompressed_bytes_received);
+
+    client.metrics().reset();
+    assert_eq!(client.metrics().snapshot().requests, 0);
+    Ok(())
+}
+
+#[test]
+fn test_compression_settings_from_env() -> Result<(), VoyageError> {
+    let config = ConfigLoader::new()
+        .with_env_vars([
+            ("VOYAGE_API_KEY", "key"),
+            ("VOYAGE_GZIP_THRESHOLD_BYTES", "2048"),
+            ("VOYAGE_ACCEPT_GZIP", "true"),
+        ])
+        .load()?;
+    assert_eq!(config.compression, CompressionConfig::gzip(2048));
+
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("key")
+        .with_gzip(1024)
+        .build()?;
+    assert_eq!(
+        client.config.config.compression,
+        CompressionConfig::gzip(1024)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_gunzip_stops_at_the_size_limit() -> Result<(), std::io::Error> {
+    // A megabyte of zeros compresses to about a kilobyte.
+    let bomb = gzip(&vec![0; 1024 * 1024])?;
+    assert!(bomb.len() < 4 * 1024);
+
+    assert_eq!(gunzip(&bomb, 1024 * 1024)?.len(), 1024 * 1024);
+    let error = gunzip(&bomb, 64 * 1024).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("65536"));
+    Ok(())
+}
@@ -0,0 +1,277 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use voyageai::client::cache::CacheConfig;
+use voyageai::client::retry::RetryConfig;
+use voyageai::config::{ConfigLoader, Settings};
+use voyageai::{
+    EmbeddingModel, RerankModel, VoyageAiClient, VoyageBuilder, VoyageConfig, VoyageError,
+};
+
+const CONFIG: &str = r#"
+[default]
+api_key = "file-key"
+embedding_model = "voyage-3-lite"
+timeout_secs = 30
+embeddings_rpm = 50
+
+[staging]
+base_url = "https://staging.example.com/v1"
+rerank_model = "rerank-2-lite"
+max_retries = 3
+timeout_secs = 2.5
+"#;
+
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("voyageai-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let path = dir.join(format!("{}.toml", name));
+    std::fs::write(&path, contents).expect("write config file");
+    path
+}
+
+#[test]
+fn test_defaults_only() -> Result<(), VoyageError> {
+    let config = ConfigLoader::new()
+        .with_overrides(Settings {
+            api_key: Some("explicit".into()),
+            ..Settings::default()
+        })
+        .load()?;
+
+    assert_eq!(config.api_key(), "explicit");
+    assert_eq!(config.base_url, "https://api.voyageai.com/v1");
+    assert_eq!(config.embedding_model, EmbeddingModel::Voyage3);
+    assert_eq!(config.timeout, None);
+    assert_eq!(config.retry, RetryConfig::default());
+    Ok(())
+}
+
+#[test]
+fn test_missing_api_key_is_an_error() {
+    let result = ConfigLoader::new()
+        .with_env_vars(Vec::<(String, String)>::new())
+        .load();
+    assert!(matches!(result, Err(VoyageError::MissingApiKey)));
+}
+
+#[test]
+fn test_file_default_table_and_profile() -> Result<(), VoyageError> {
+    let path = config_file("profiles", CONFIG);
+
+    let base = ConfigLoader::new().with_file(&path).load()?;
+    assert_eq!(base.api_key(), "file-key");
+    assert_eq!(base.embedding_model, EmbeddingModel::Voyage3Lite);
+    assert_eq!(base.timeout, Some(Duration::from_secs(30)));
+    assert_eq!(base.rate_limits.embeddings_rpm, 50);
+    assert_eq!(base.retry.max_retries, 0);
+
+    let staging = ConfigLoader::new()
+        .with_file(&path)
+        .with_profile("staging")
+        .load()?;
+    assert_eq!(staging.api_key(), "file-key");
+    assert_eq!(staging.embedding_model, EmbeddingModel::Voyage3Lite);
+    assert_eq!(staging.base_url, "https://staging.example.com/v1");
+    assert_eq!(staging.rerank_model, RerankModel::Rerank2Lite);
+    assert_eq!(staging.retry.max_retries, 3);
+    assert_eq!(staging.timeout, Some(Duration::from_millis(2500)));
+    Ok(())
+}
+
+#[test]
+fn test_layers_take_precedence_in_order() -> Result<(), VoyageError> {
+    let path = config_file("layers", CONFIG);
+
+    let config = ConfigLoader::new()
+        .with_file(&path)
+        .with_env_vars([
+            ("VOYAGE_PROFILE", "staging"),
+            ("VOYAGE_API_KEY", "env-key"),
+            ("VOYAGE_MAX_RETRIES", "5"),
+            ("VOYAGE_EMBEDDING_MODEL", "voyage-law-2"),
+        ])
+        .with_overrides(Settings {
+            max_retries: Some(7),
+            ..Settings::default()
+        })
+        .load()?;
+
+    assert_eq!(config.base_url, "https://staging.example.com/v1");
+    assert_eq!(config.api_key(), "env-key");
+    assert_eq!(config.embedding_model, EmbeddingModel::VoyageLaw2);
+    assert_eq!(config.retry.max_retries, 7);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_inputs_are_config_errors() {
+    let unknown_key = config_file("unknown", "[default]\napi_kee = \"typo\"\n");
+    let result = ConfigLoader::new().with_file(&unknown_key).load();
+    assert!(matches!(result, Err(VoyageError::ConfigError(_))));
+
+    let path = config_file("missing-profile", CONFIG);
+    let result = ConfigLoader::new()
+        .with_file(&path)
+        .with_profile("production")
+        .load();
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("production")));
+
+    let result = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY", "k"), ("VOYAGE_MAX_RETRIES", "lots")])
+        .load();
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("VOYAGE_MAX_RETRIES")));
+
+    let result = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY", "k"), ("VOYAGE_RERANK_MODEL", "rerank-9")])
+        .load();
+    assert!(matches!(result, Err(VoyageError::ConfigError(_))));
+}
+
+#[test]
+fn test_builder_calls_override_file() -> Result<(), VoyageError> {
+    let path = config_file("builder", CONFIG);
+
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_config_file(&path)
+        .with_profile("staging")
+        .with_base_url("http://localhost:9999/v1")
+        .with_embedding_model(EmbeddingModel::VoyageFinance2)
+        .with_timeout(Duration::from_secs(5))
+        .build()?;
+
+    let config = &client.config.config;
+    assert_eq!(config.api_key(), "file-key");
+    assert_eq!(config.base_url, "http://localhost:9999/v1");
+    assert_eq!(config.embedding_model, EmbeddingModel::VoyageFinance2);
+    assert_eq!(config.rerank_model, RerankModel::Rerank2Lite);
+    assert_eq!(config.timeout, Some(Duration::from_secs(5)));
+    assert_eq!(config.retry.max_retries, 3);
+    Ok(())
+}
+
+#[test]
+fn test_loader_debug_hides_secrets() {
+    let loader = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY", "pa-env-secret")])
+        .with_overrides(Settings {
+            api_key: Some("pa-override-secret".into()),
+            ..Settings::default()
+        });
+
+    let debug = format!("{:?}", loader);
+    assert!(!debug.contains("pa-env-secret"));
+    assert!(!debug.contains("pa-override-secret"));
+}
+
+#[test]
+fn test_api_key_file_and_command_sources() -> Result<(), VoyageError> {
+    let key_file = config_file("secret", "  pa-from-file\n");
+
+    let config = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY_FILE", key_file.to_string_lossy())])
+        .load()?;
+    assert_eq!(config.api_key(), "pa-from-file");
+
+    let config = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY_COMMAND", "echo pa-from-command")])
+        .load()?;
+    assert_eq!(config.api_key(), "pa-from-command");
+
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key_file(&key_file)
+        .build()?;
+    assert_eq!(client.config.config.api_key(), "pa-from-file");
+    Ok(())
+}
+
+#[test]
+fn test_higher_layer_key_source_replaces_lower_key() -> Result<(), VoyageError> {
+    let path = config_file("key-source", CONFIG);
+
+    let config = ConfigLoader::new()
+        .with_file(&path)
+        .with_env_vars([("VOYAGE_API_KEY_COMMAND", "echo pa-from-env-command")])
+        .load()?;
+
+    assert_eq!(config.api_key(), "pa-from-env-command");
+    Ok(())
+}
+
+#[test]
+fn test_builder_key_replaces_file_key_pool() -> Result<(), VoyageError> {
+    let path = config_file(
+        "key-pool",
+        "[default]\napi_keys = [\"pa-one\", \"pa-two\"]\nkey_selection = \"failover\"\n",
+    );
+
+    let config = ConfigLoader::new().with_file(&path).load()?;
+    assert!(config.key_pool.is_some());
+
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_config_file(&path)
+        .with_api_key("pa-builder")
+        .build()?;
+    assert_eq!(client.config.config.api_key(), "pa-builder");
+    assert!(client.config.config.key_pool.is_none());
+
+    let config = ConfigLoader::new()
+        .with_file(&path)
+        .with_env_vars([("VOYAGE_API_KEY", "pa-env")])
+        .load()?;
+    assert_eq!(config.api_key(), "pa-env");
+    assert!(config.key_pool.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_key_sources_fail_without_panicking() {
+    let result = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY_FILE", "/nonexistent/voyage-key")])
+        .load();
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("/nonexistent")));
+
+    let result = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY_COMMAND", "echo nope >&2; exit 3")])
+        .load();
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("nope")));
+
+    let empty = config_file("empty-secret", "\n");
+    let result = ConfigLoader::new()
+        .with_env_vars([("VOYAGE_API_KEY_FILE", empty.to_string_lossy())])
+        .load();
+    assert!(matches!(result, Err(VoyageError::MissingApiKey)));
+
+    let result = VoyageAiClient::new_with_config(VoyageConfig::new(""));
+    assert!(matches!(result, Err(VoyageError::MissingApiKey)));
+    let result = VoyageBuilder::with_loader(ConfigLoader::new()).build();
+    assert!(matches!(result, Err(VoyageError::MissingApiKey)));
+}
+
+#[test]
+fn test_cache_settings_from_every_layer() -> Result<(), VoyageError> {
+    let path = config_file("cache", "[default]\napi_key = \"k\"\ncache_capacity = 10\n");
+
+    let from_file = ConfigLoader::new().with_file(&path).load()?;
+    let from_env = ConfigLoader::new()
+        .with_file(&path)
+        .with_env_vars([("VOYAGE_CACHE_TTL_SECS", "60")])
+        .load()?;
+    let disabled = ConfigLoader::new()
+        .with_file(&path)
+        .with_env_vars([("VOYAGE_CACHE_TTL_SECS", "60")])
+        .with_overrides(Settings {
+            cache_capacity: Some(0),
+            ..Settings::default()
+        })
+        .load()?;
+
+    assert_eq!(from_file.cache, Some(CacheConfig::new(10)));
+    assert_eq!(
+        from_env.cache,
+        Some(CacheConfig::new(10).with_ttl(Duration::from_secs(60)))
+    );
+    assert_eq!(disabled.cache, None);
+    assert_eq!(VoyageConfig::new("k").cache, None);
+    Ok(())
+}
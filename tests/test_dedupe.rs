@@ -0,0 +1,50 @@
+use voyageai::client::dedupe::dedupe_inputs;
+use voyageai::VoyageConfig;
+
+fn texts(values: &[&str]) -> Vec<String> {
+    values.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_dedupe_collapses_identical_inputs() {
+    let inputs = texts(&["header", "body one", "header", "body two", "header"]);
+
+    let deduped = dedupe_inputs(&inputs);
+
+    assert_eq!(deduped.unique, texts(&["header", "body one", "body two"]));
+    assert_eq!(deduped.positions, vec![0, 1, 0, 2, 0]);
+    assert_eq!(deduped.inputs_saved(), 2);
+    assert!(deduped.has_duplicates());
+}
+
+#[test]
+fn test_dedupe_without_duplicates_is_identity() {
+    let inputs = texts(&["a", "b", "c"]);
+
+    let deduped = dedupe_inputs(&inputs);
+
+    assert_eq!(deduped.unique, inputs);
+    assert_eq!(deduped.positions, vec![0, 1, 2]);
+    assert!(!deduped.has_duplicates());
+}
+
+#[test]
+fn test_fan_out_restores_original_order() {
+    let inputs = texts(&["x", "y", "x", "x", "y"]);
+    let deduped = dedupe_inputs(&inputs);
+    let unique_embeddings = vec![vec![1.0_f32], vec![2.0_f32]];
+
+    let fanned = deduped.fan_out(&unique_embeddings);
+
+    assert_eq!(
+        fanned,
+        vec![vec![1.0], vec![2.0], vec![1.0], vec![1.0], vec![2.0]]
+    );
+}
+
+#[test]
+fn test_dedupe_disabled_by_default() {
+    let config = VoyageConfig::new("test_key".to_string());
+    assert!(!config.dedupe_inputs);
+    assert!(config.with_dedupe_inputs(true).dedupe_inputs);
+}
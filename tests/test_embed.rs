@@ -6,7 +6,7 @@ use voyageai::{InputType, VoyageAiClient, VoyageConfig};
 async fn test_embedding() -> Result<(), Box<dyn std::error::Error>> {
     let api_key = std::env::var("VOYAGE_API_KEY").unwrap_or_else(|_| "test_key".to_string());
     let config = VoyageConfig::new(api_key);
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     let inputs = [
         "Soul music emerged in the 1950s.",
@@ -50,7 +50,7 @@ async fn test_embedding() -> Result<(), Box<dyn std::error::Error>> {
 async fn test_embedding_single_input() {
     let api_key = std::env::var("VOYAGE_API_KEY").unwrap_or_else(|_| "test_key".to_string());
     let config = VoyageConfig::new(api_key);
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config).expect("client with API key");
 
     let input = "Soul rock music combines elements of both genres.";
 
@@ -0,0 +1,115 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::client;
+use futures::stream::{self, StreamExt};
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::client::EmbedStreamOptions;
+use voyageai::VoyageError;
+
+fn rows(count: usize) -> Vec<(String, String)> {
+    (0..count)
+        .map(|i| (format!("row-{}", i), format!("text number {}", i)))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_stream_batches_and_keeps_order() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server);
+
+    let results: Vec<_> = client
+        .embed_stream(stream::iter(rows(300)), EmbedStreamOptions::default())
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 300);
+    let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+    let expected: Vec<String> = rows(300).into_iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, expected);
+
+    let batch_sizes: Vec<usize> = server
+        .requests()
+        .iter()
+        .map(|r| r.json().map(|body| body["input"].as_array().unwrap().len()))
+        .collect::<Result<_, _>>()?;
+    let mut sorted = batch_sizes.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, [44, 128, 128]);
+
+    let single = client.embed("text number 150").await?;
+    assert_eq!(results[150].1.as_ref().unwrap(), &single);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stream_pulls_inputs_lazily() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server);
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let counter = pulled.clone();
+    let inputs = stream::iter(rows(10_000)).inspect(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let options = EmbedStreamOptions::default()
+        .with_batch_size(10)
+        .with_concurrency(2);
+    let first: Vec<_> = client.embed_stream(inputs, options).take(1).collect().await;
+
+    assert_eq!(first[0].0, "row-0");
+    assert!(pulled.load(Ordering::SeqCst) <= 30);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_batch_fails_only_its_inputs() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.enqueue(MockResponse::status(400));
+    let client = client(&server);
+
+    let options = EmbedStreamOptions::default()
+        .with_batch_size(2)
+        .with_concurrency(1);
+    let results: Vec<_> = client
+        .embed_stream(stream::iter(rows(4)), options)
+        .collect()
+        .await;
+
+    let (first, second) = (&results[0].1, &results[1].1);
+    match (first, second) {
+        (Err(a), Err(b)) => {
+            assert!(Arc::ptr_eq(a, b));
+            assert!(matches!(**a, VoyageError::BadRequest { .. }));
+        }
+        other => panic!("expected the first batch to fail, got {:?}", other),
+    }
+    assert!(results[2].1.is_ok());
+    assert!(results[3].1.is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unordered_text_stream_yields_every_index() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server);
+    let texts = stream::iter((0..50).map(|i| format!("text {}", i)));
+
+    let options = EmbedStreamOptions::default()
+        .with_batch_size(7)
+        .with_ordered(false);
+    let mut indices: Vec<usize> = client
+        .embed_text_stream(texts, options)
+        .map(|(index, result)| {
+            assert_eq!(result.expect("embedding").len(), 512);
+            index
+        })
+        .collect()
+        .await;
+
+    indices.sort_unstable();
+    assert_eq!(indices, (0..50).collect::<Vec<_>>());
+    Ok(())
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{client, client_with_config};
+use voyageai::builder::search::SearchRequestBuilder;
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::search_client::SearchClient;
+use voyageai::client::MockVoyageClient;
+use voyageai::traits::llm::Embedder;
+use voyageai::{EmbeddingModel, SearchModel, SearchType, VoyageError};
+
+#[tokio::test]
+async fn test_query_and_document_input_types() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server);
+
+    let query = client.embed_query("what is rust?").await?;
+    let documents = client
+        .embed_documents(&["Rust is a language".to_string()])
+        .await?;
+    let untyped = Embedder::embed(&client, "plain").await?;
+
+    assert_eq!(query.len(), 512);
+    assert_eq!(documents.len(), 1);
+    assert_eq!(untyped.len(), 512);
+    let requests = server.requests();
+    let bodies: Vec<_> = requests.iter().map(|r| r.json().unwrap()).collect();
+    assert_eq!(bodies[0]["input_type"], "query");
+    assert_eq!(bodies[0]["model"], "voyage-3-lite");
+    assert_eq!(bodies[1]["input_type"], "document");
+    assert!(bodies[2].get("input_type").is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_documents_make_no_request() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server);
+
+    assert!(client.embed_documents(&[]).await?.is_empty());
+    assert!(server.requests().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_model_metadata() {
+    let config = voyageai::VoyageConfig::new("test_key".to_string())
+        .with_embedding_model(EmbeddingModel::VoyageLaw2);
+    let client = client_with_config(config);
+    assert_eq!(Embedder::model_name(&client), "voyage-law-2");
+    assert_eq!(client.dimension(), 1024);
+
+    let mock = MockVoyageClient::new().with_embedding_model(EmbeddingModel::Voyage3);
+    assert_eq!(mock.model_name(), "voyage-3");
+    assert_eq!(mock.dimension(), 1024);
+}
+
+#[test]
+fn test_embedding_dimensions_match_the_api() {
+    let dimensions = [
+        (EmbeddingModel::Voyage3, 1024),
+        (EmbeddingModel::Voyage3Lite, 512),
+        (EmbeddingModel::VoyageFinance2, 1024),
+        (EmbeddingModel::VoyageMultilingual2, 1024),
+        (EmbeddingModel::VoyageLaw2, 1024),
+    ];
+    for (model, dimension) in dimensions {
+        assert_eq!(model.embedding_dimension(), dimension, "{:?}", model);
+    }
+}
+
+/// Generic code can size buffers from the embedder before calling it.
+async fn embed_into_matrix<E: Embedder>(
+    embedder: &E,
+    texts: &[String],
+) -> Result<Vec<f32>, VoyageError> {
+    let mut matrix = Vec::with_capacity(texts.len() * embedder.dimension());
+    for embedding in embedder.embed_documents(texts).await? {
+        assert_eq!(embedding.len(), embedder.dimension());
+        matrix.extend(embedding);
+    }
+    Ok(matrix)
+}
+
+#[tokio::test]
+async fn test_generic_embedder_and_search() -> Result<(), VoyageError> {
+    let mock = MockVoyageClient::new();
+    let texts = vec!["rust borrow checker".to_string(), "python gil".to_string()];
+    let matrix = embed_into_matrix(&mock, &texts).await?;
+    assert_eq!(matrix.len(), 2 * mock.dimension());
+
+    let search = SearchClient::new(mock.clone(), mock.clone());
+    let request = SearchRequestBuilder::new()
+        .query("rust borrow checker")
+        .documents(texts)
+        .model(SearchModel::default())
+        .search_type(SearchType::NearestNeighbor)
+        .build()?;
+    let results = search.search(&request).await?;
+
+    assert_eq!(results[0].document, ["rust borrow checker"]);
+    assert_eq!(search.embedder().calls(), mock.calls());
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use voyageai::builder::search::SearchRequestBuilder;
+use voyageai::builder::voyage::VoyageBuilder;
+use voyageai::client::search_client::SearchClient;
+use voyageai::client::{EmbedStreamOptions, EmbeddingsClient, MockVoyageClient};
+use voyageai::config::ConfigLoader;
+use voyageai::models::embeddings::{EmbeddingData, EmbeddingsRequest, EmbeddingsResponse, Usage};
+use voyageai::models::ResponseMetadata;
+use voyageai::traits::llm::Embedder;
+use voyageai::{
+    EmbeddingModel, EmbeddingsInput, EmbeddingsRequestBuilder, InputType, SearchModel, SearchType,
+    VoyageAiClient, VoyageError,
+};
+
+/// Embeds texts mentioning "rust" as `[1, 0]` and everything else as `[0, 1]`, and
+/// records the requests it receives.
+#[derive(Debug, Default)]
+struct StubEmbeddings {
+    requests: Mutex<Vec<EmbeddingsRequest>>,
+}
+
+#[async_trait]
+impl EmbeddingsClient for StubEmbeddings {
+    fn embedding_model(&self) -> EmbeddingModel {
+        EmbeddingModel::Voyage3Lite
+    }
+
+    async fn create_embedding(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, VoyageError> {
+        self.requests.lock().unwrap().push(request.clone());
+        let texts = match &request.input {
+            EmbeddingsInput::Single(text) => vec![text.clone()],
+            EmbeddingsInput::Multiple(texts) => texts.clone(),
+        };
+        let data = texts
+            .iter()
+            .enumerate()
+            .map(|(index, text)| EmbeddingData {
+                object: "embedding".to_string(),
+                embedding: if text.contains("rust") {
+                    vec![1.0, 0.0]
+                } else {
+                    vec![0.0, 1.0]
+                },
+                index,
+            })
+            .collect();
+        Ok(EmbeddingsResponse {
+            object: "list".to_string(),
+            data,
+            model: request.model.as_str().to_string(),
+            usage: Usage { total_tokens: 1 },
+            dedupe: None,
+            split: Vec::new(),
+            served_by: None,
+            metadata: ResponseMetadata::default(),
+        })
+    }
+}
+
+fn search_request(query: &str, documents: &[&str]) -> voyageai::client::SearchRequest {
+    SearchRequestBuilder::new()
+        .query(query)
+        .documents(documents.iter().map(|d| d.to_string()))
+        .model(SearchModel::default())
+        .search_type(SearchType::Similarity)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_search_client_runs_on_a_stub() -> Result<(), VoyageError> {
+    let stub = Arc::new(StubEmbeddings::default());
+    let embeddings: Arc<dyn EmbeddingsClient> = stub.clone();
+    let search = SearchClient::new(embeddings, MockVoyageClient::new());
+
+    let results = search
+        .search(&search_request("rust", &["python tutorial", "rust book"]))
+        .await?;
+
+    assert_eq!(results[0].document, ["rust book"]);
+    let requests = stub.requests.lock().unwrap();
+    assert_eq!(requests[0].input_type, Some(InputType::Query));
+    assert_eq!(requests[1].input_type, Some(InputType::Document));
+    assert_eq!(requests[1].model, EmbeddingModel::Voyage3Lite);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_builder_routes_embeddings_and_search_to_client() -> Result<(), VoyageError> {
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("unused")
+        .with_embeddings_client(StubEmbeddings::default())
+        .build()?;
+
+    let batch = client
+        .embed_batch(vec!["rust".to_string(), "go".to_string()])
+        .await?;
+    assert_eq!(batch, [vec![1.0, 0.0], vec![0.0, 1.0]]);
+    assert_eq!(Embedder::dimension(&client), 512);
+
+    let results = client
+        .search(search_request("rust", &["go", "rust"]))
+        .await?;
+    assert_eq!(results[0].index, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chain_accepts_an_embeddings_client() -> Result<(), VoyageError> {
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("unused")
+        .build()?;
+    let stub = Arc::new(StubEmbeddings::default());
+
+    let request = client
+        .chain()
+        .with_embeddings_client(stub.clone())
+        .embed_documents(vec!["rust".to_string()])
+        .await
+        .search("rust")
+        .await
+        .execute()
+        .await
+        .expect("search request");
+
+    assert_eq!(request.embeddings, Some(vec![vec![1.0, 0.0]]));
+    assert_eq!(stub.requests.lock().unwrap().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_isolated_streaming_and_bulk_embedding_use_the_client() -> Result<(), VoyageError> {
+    let stub = Arc::new(StubEmbeddings::default());
+    let client = VoyageAiClient::with_key("unused")?.with_embeddings_client(stub.clone());
+
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(["rust", "go"])
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    let batch = client.embeddings_isolated(request).await?;
+    assert_eq!(batch.failures(), 0);
+
+    let texts = stream::iter([(1, "rust".to_string()), (2, "go".to_string())]);
+    let results: Vec<_> = client
+        .embed_stream(texts, EmbedStreamOptions::default())
+        .collect()
+        .await;
+    assert!(matches!(&results[0], (1, Ok(embedding)) if embedding == &[1.0, 0.0]));
+
+    let dir = std::env::temp_dir().join(format!("voyageai-stub-bulk-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let input = dir.join("input.jsonl");
+    std::fs::write(&input, "{\"id\": 1, \"text\": \"rust\"}\n").expect("write input");
+    let output = dir.join("output.jsonl");
+    let report = client.bulk_job(&input, &output).run().await?;
+    assert_eq!(report.records, 1);
+
+    assert_eq!(stub.requests.lock().unwrap().len(), 3);
+    Ok(())
+}
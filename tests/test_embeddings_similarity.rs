@@ -11,7 +11,7 @@ mod tests {
         dotenv().ok();
         let api_key = std::env::var("VOYAGE_API_KEY").expect("VOYAGE_API_KEY must be set");
         let config = VoyageConfig::new(api_key);
-        let client = VoyageAiClient::new_with_config(config);
+        let client = VoyageAiClient::new_with_config(config).expect("client with API key");
 
         let texts = [
             "The quick brown fox jumps over the lazy dog",
@@ -0,0 +1,252 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use reqwest::header::HeaderValue;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::client::transport::RawResponse;
+use voyageai::client::RateLimiter;
+use voyageai::{
+    EmbeddingModel, EmbeddingsRequestBuilder, InterceptAction, InterceptedRequest, Interceptor,
+    VoyageBuilder, VoyageError,
+};
+
+#[derive(Debug)]
+struct TagHeader;
+
+#[async_trait]
+impl Interceptor for TagHeader {
+    async fn before_request(
+        &self,
+        request: &mut InterceptedRequest,
+    ) -> Result<InterceptAction, VoyageError> {
+        request
+            .headers
+            .insert("x-request-tag", HeaderValue::from_static("audit"));
+        Ok(InterceptAction::Continue)
+    }
+}
+
+#[derive(Debug)]
+struct RedactEmails;
+
+#[async_trait]
+impl Interceptor for RedactEmails {
+    async fn before_request(
+        &self,
+        request: &mut InterceptedRequest,
+    ) -> Result<InterceptAction, VoyageError> {
+        if let Some(Value::Array(inputs)) = request.body.get_mut("input") {
+            for input in inputs.iter_mut() {
+                if input.as_str().is_some_and(|text| text.contains('@')) {
+                    *input = Value::String("<email>".to_string());
+                }
+            }
+        }
+        Ok(InterceptAction::Continue)
+    }
+}
+
+#[derive(Debug)]
+struct CannedResponse;
+
+#[async_trait]
+impl Interceptor for CannedResponse {
+    async fn before_request(
+        &self,
+        _request: &mut InterceptedRequest,
+    ) -> Result<InterceptAction, VoyageError> {
+        let body = json!({
+            "object": "list",
+            "data": [{"object": "embedding", "embedding": [1.0, 0.0], "index": 0}],
+            "model": "voyage-3",
+            "usage": {"total_tokens": 1}
+        });
+        Ok(InterceptAction::Respond(RawResponse {
+            status: StatusCode::OK,
+            headers: Default::default(),
+            body: body.to_string(),
+        }))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Recorder {
+    name: &'static str,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for Recorder {
+    async fn before_request(
+        &self,
+        request: &mut InterceptedRequest,
+    ) -> Result<InterceptAction, VoyageError> {
+        self.push(format!("{} before {}", self.name, request.path));
+        Ok(InterceptAction::Continue)
+    }
+
+    async fn after_response(
+        &self,
+        _request: &InterceptedRequest,
+        response: &mut RawResponse,
+    ) -> Result<(), VoyageError> {
+        self.push(format!("{} after {}", self.name, response.status.as_u16()));
+        Ok(())
+    }
+
+    async fn on_error(&self, _request: &InterceptedRequest, error: &VoyageError) {
+        self.push(format!("{} error {}", self.name, error));
+    }
+}
+
+impl Recorder {
+    fn push(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[derive(Debug)]
+struct Deny;
+
+#[async_trait]
+impl Interceptor for Deny {
+    async fn before_request(
+        &self,
+        _request: &mut InterceptedRequest,
+    ) -> Result<InterceptAction, VoyageError> {
+        Err(VoyageError::Forbidden("blocked by policy".to_string()))
+    }
+}
+
+fn embeddings_request(texts: &[&str]) -> voyageai::models::embeddings::EmbeddingsRequest {
+    EmbeddingsRequestBuilder::new()
+        .documents(texts.iter().copied())
+        .model(EmbeddingModel::Voyage3)
+        .build()
+        .expect("valid embeddings request")
+}
+
+#[tokio::test]
+async fn test_interceptors_modify_headers_and_body() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let config = server
+        .config("test_key")
+        .with_interceptor(Arc::new(TagHeader))
+        .with_interceptor(Arc::new(RedactEmails));
+    let client = EmbeddingClient::new(config, Arc::new(RateLimiter::new()))?;
+
+    client
+        .create_embedding(&embeddings_request(&["hello", "bob@example.com"]))
+        .await?;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("x-request-tag"), Some("audit"));
+    let body = requests[0].json()?;
+    assert_eq!(body["input"], json!(["hello", "<email>"]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_interceptor_can_short_circuit() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let config = server
+        .config("test_key")
+        .with_interceptor(Arc::new(CannedResponse))
+        .with_interceptor(Arc::new(Recorder {
+            name: "second",
+            events: events.clone(),
+        }));
+    let client = EmbeddingClient::new(config, Arc::new(RateLimiter::new()))?;
+
+    let response = client
+        .create_embedding(&embeddings_request(&["hello"]))
+        .await?;
+
+    assert_eq!(response.data[0].embedding, vec![1.0, 0.0]);
+    assert!(server.requests().is_empty());
+    assert_eq!(*events.lock().unwrap(), vec!["second after 200"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_interceptors_run_in_order_and_see_errors() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.enqueue(MockResponse::status(500).with_body("boom"));
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let config = server
+        .config("test_key")
+        .with_interceptor(Arc::new(Recorder {
+            name: "first",
+            events: events.clone(),
+        }))
+        .with_interceptor(Arc::new(Recorder {
+            name: "second",
+            events: events.clone(),
+        }));
+    let client = EmbeddingClient::new(config, Arc::new(RateLimiter::new()))?;
+
+    let result = client
+        .create_embedding(&embeddings_request(&["hello"]))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(VoyageError::InternalServerError { .. })
+    ));
+    let events = events.lock().unwrap();
+    assert_eq!(
+        events[..4],
+        [
+            "first before /embeddings",
+            "second before /embeddings",
+            "first after 500",
+            "second after 500",
+        ]
+    );
+    assert!(events[4].starts_with("first error Internal Server Error"));
+    assert!(events[5].starts_with("second error Internal Server Error"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_interceptor_error_aborts_request() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let config = server
+        .config("test_key")
+        .with_interceptor(Arc::new(Deny))
+        .with_interceptor(Arc::new(Recorder {
+            name: "audit",
+            events: events.clone(),
+        }));
+    let client = EmbeddingClient::new(config, Arc::new(RateLimiter::new()))?;
+
+    let result = client
+        .create_embedding(&embeddings_request(&["hello"]))
+        .await;
+
+    assert!(matches!(result, Err(VoyageError::Forbidden(_))));
+    assert!(server.requests().is_empty());
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["audit error Forbidden (403): blocked by policy"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_builder_registers_interceptors() -> Result<(), VoyageError> {
+    let client = VoyageBuilder::new()
+        .with_interceptor(TagHeader)
+        .with_api_key("test_key")
+        .with_interceptor(Recorder::default())
+        .build()?;
+
+    assert_eq!(client.config.config.interceptors.len(), 2);
+    Ok(())
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use std::fs;
+
+use common::client;
+use serde_json::Value;
+use voyageai::bulk::BulkJob;
+use voyageai::client::mock_server::{MockResponse, MockServer, RecordedRequest};
+use voyageai::models::embeddings::EmbeddingsRequest;
+use voyageai::{EmbeddingModel, EmbeddingsInput, VoyageError};
+
+/// Rejects any request containing an input that starts with "BAD".
+fn reject_bad_inputs(request: &RecordedRequest) -> Result<(), String> {
+    let body = request.json().map_err(|e| e.to_string())?;
+    let inputs = body["input"].as_array().cloned().unwrap_or_default();
+    match inputs
+        .iter()
+        .find(|i| i.as_str().is_some_and(|s| s.starts_with("BAD")))
+    {
+        Some(input) => Err(format!("input too long: {}", input)),
+        None => Ok(()),
+    }
+}
+
+fn request(texts: &[&str]) -> EmbeddingsRequest {
+    EmbeddingsRequest {
+        input: EmbeddingsInput::Multiple(texts.iter().map(|t| t.to_string()).collect()),
+        model: EmbeddingModel::Voyage3Lite,
+        input_type: None,
+        truncation: None,
+        encoding_format: None,
+    }
+}
+
+#[tokio::test]
+async fn test_isolates_bad_inputs() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.assert_each(reject_bad_inputs);
+    let client = client(&server);
+    let texts = ["a", "b", "BAD c", "d", "e", "f", "BAD g", "h"];
+
+    let batch = client.create_embedding_isolated(&request(&texts)).await?;
+
+    assert_eq!(batch.items.len(), 8);
+    assert_eq!(batch.failures(), 2);
+    for (text, item) in texts.iter().zip(&batch.items) {
+        match item {
+            Ok(embedding) => {
+                assert!(!text.starts_with("BAD"));
+                assert_eq!(embedding, &client.embed(text).await?);
+            }
+            Err(VoyageError::BadRequest { .. }) => assert!(text.starts_with("BAD")),
+            Err(other) => panic!("unexpected error {:?}", other),
+        }
+    }
+    assert!(batch.total_tokens > 0);
+    assert_eq!(batch.requests, 11);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clean_batch_is_one_request() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server);
+
+    let batch = client
+        .create_embedding_isolated(&request(&["a", "b", "c"]))
+        .await?;
+
+    assert_eq!(batch.requests, 1);
+    assert_eq!(batch.failures(), 0);
+    assert_eq!(server.requests().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_other_errors_fail_the_batch() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.enqueue(MockResponse::status(401));
+    let client = client(&server);
+
+    let result = client
+        .create_embedding_isolated(&request(&["a", "b"]))
+        .await;
+
+    assert!(matches!(result, Err(VoyageError::Unauthorized)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_job_dead_letters_rejected_records() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.assert_each(reject_bad_inputs);
+    let dir = std::env::temp_dir().join(format!("voyageai-isolation-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let input = dir.join("input.jsonl");
+    let output = dir.join("output.jsonl");
+    let dead_letter = dir.join("rejected.jsonl");
+    let texts = ["one", "BAD two", "three", "four", "BAD five"];
+    let lines: String = texts
+        .iter()
+        .enumerate()
+        .map(|(i, text)| format!("{{\"id\": {}, \"text\": \"{}\"}}\n", i, text))
+        .collect();
+    fs::write(&input, lines)?;
+
+    let report = BulkJob::new(client(&server), &input, &output)
+        .with_batch_size(3)
+        .with_dead_letter(&dead_letter)
+        .run()
+        .await?;
+
+    assert_eq!((report.records, report.failed), (5, 2));
+    let read = |path| -> Vec<Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    };
+    let embedded: Vec<Value> = read(&output).iter().map(|l| l["id"].clone()).collect();
+    assert_eq!(embedded, ["0", "2", "3"]);
+    let rejected = read(&dead_letter);
+    assert_eq!(rejected.len(), 2);
+    assert_eq!(rejected[0]["id"], "1");
+    assert_eq!(rejected[0]["text"], "BAD two");
+    assert!(rejected[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("input too long"));
+    Ok(())
+}
@@ -0,0 +1,208 @@
+mod common;
+
+use voyageai::client::client_limiter::{Endpoint, RateLimits};
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::client::retry::RetryConfig;
+use voyageai::client::{KeyPool, KeySelection};
+use voyageai::config::ConfigLoader;
+use voyageai::{EmbeddingModel, EmbeddingsRequestBuilder, VoyageBuilder, VoyageError};
+
+use common::client_with_config;
+use std::time::Duration;
+
+const KEYS: [&str; 3] = [
+    "pa-first-key-0000-aaaa",
+    "pa-second-key-000-bbbb",
+    "pa-third-key-0000-cccc",
+];
+
+fn pooled_client(server: &MockServer, selection: KeySelection) -> (EmbeddingClient, KeyPool) {
+    retrying_pooled_client(server, selection, 0)
+}
+
+fn retrying_pooled_client(
+    server: &MockServer,
+    selection: KeySelection,
+    max_retries: u32,
+) -> (EmbeddingClient, KeyPool) {
+    let pool = KeyPool::new(KEYS, selection).expect("valid key pool");
+    let retry = RetryConfig {
+        max_retries,
+        initial_delay: Duration::from_millis(1),
+        ..RetryConfig::default()
+    };
+    let config = server
+        .config("unused")
+        .with_retry(retry)
+        .with_key_pool(pool.clone());
+    (client_with_config(config), pool)
+}
+
+async fn embed(client: &EmbeddingClient, text: &str) -> Result<Option<String>, VoyageError> {
+    let request = EmbeddingsRequestBuilder::new()
+        .input(text)
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    Ok(client.create_embedding(&request).await?.served_by)
+}
+
+fn bearer_keys(server: &MockServer) -> Vec<String> {
+    server
+        .requests()
+        .iter()
+        .map(|r| {
+            let header = r.header("Authorization").unwrap_or_default();
+            header.trim_start_matches("Bearer ").to_string()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_round_robin_cycles_keys() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let (client, _) = pooled_client(&server, KeySelection::RoundRobin);
+
+    for text in ["a", "b", "c", "d"] {
+        embed(&client, text).await?;
+    }
+
+    assert_eq!(bearer_keys(&server), [KEYS[0], KEYS[1], KEYS[2], KEYS[0]]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failover_moves_on_after_rate_limit() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    // The long Retry-After is not waited out: the request moves to the next key at once.
+    server.enqueue(MockResponse::status(429).with_retry_after(60));
+    let (client, _) = retrying_pooled_client(&server, KeySelection::Failover, 3);
+
+    let served_by = embed(&client, "hello").await?;
+    assert_eq!(served_by.as_deref(), Some("#1 …bbbb"));
+
+    // Later requests stay on the key that answered.
+    embed(&client, "again").await?;
+    assert_eq!(bearer_keys(&server), [KEYS[0], KEYS[1], KEYS[1]]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failover_retries_rate_limits_on_the_last_key() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    for _ in 0..4 {
+        server.enqueue(MockResponse::status(429).with_retry_after(0));
+    }
+    let (client, _) = retrying_pooled_client(&server, KeySelection::Failover, 3);
+
+    embed(&client, "hello").await?;
+
+    assert_eq!(
+        bearer_keys(&server),
+        [KEYS[0], KEYS[1], KEYS[2], KEYS[2], KEYS[2]]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejected_key_is_quarantined() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.expect_api_key(KEYS[1]);
+    let (client, pool) = pooled_client(&server, KeySelection::RoundRobin);
+
+    embed(&client, "a").await?;
+    embed(&client, "b").await?;
+    embed(&client, "c").await?;
+
+    assert_eq!(
+        bearer_keys(&server),
+        [KEYS[0], KEYS[1], KEYS[2], KEYS[1], KEYS[1]]
+    );
+    assert_eq!(pool.quarantined(), ["#0 …aaaa", "#2 …cccc"]);
+
+    pool.reinstate_all();
+    assert!(pool.quarantined().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_all_keys_rejected_is_unauthorized() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.expect_api_key("pa-some-other-key-zzzz");
+    let (client, pool) = pooled_client(&server, KeySelection::Failover);
+
+    let result = embed(&client, "a").await;
+
+    assert!(matches!(result, Err(VoyageError::Unauthorized)));
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(pool.quarantined().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_least_used_prefers_idle_keys() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let (client, _) = pooled_client(&server, KeySelection::LeastUsed);
+
+    embed(&client, "a rather longer text that uses more tokens").await?;
+    embed(&client, "b").await?;
+    embed(&client, "c").await?;
+
+    assert_eq!(bearer_keys(&server), [KEYS[0], KEYS[1], KEYS[2]]);
+    Ok(())
+}
+
+#[test]
+fn test_pool_rejects_blank_keys() {
+    let result = KeyPool::new(Vec::<String>::new(), KeySelection::RoundRobin);
+    assert!(matches!(result, Err(VoyageError::MissingApiKey)));
+    let result = KeyPool::new(["pa-ok", " "], KeySelection::RoundRobin);
+    assert!(matches!(result, Err(VoyageError::MissingApiKey)));
+}
+
+#[tokio::test]
+async fn test_pool_keys_use_configured_rate_limits() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let pool = KeyPool::new(KEYS, KeySelection::Failover)?;
+    let limits = RateLimits {
+        embeddings_rpm: 1,
+        ..RateLimits::default()
+    };
+    let config = server
+        .config("unused")
+        .with_rate_limits(limits)
+        .with_key_pool(pool.clone());
+    let client = client_with_config(config);
+
+    embed(&client, "hello").await?;
+
+    let key = pool
+        .select(Endpoint::Embeddings, &[])
+        .await?
+        .expect("a key in service");
+    assert_eq!(key.index(), 0);
+    let wait = key.limiter().check_limit(Endpoint::Embeddings, 1).await;
+    assert!(!wait.is_zero(), "the key allows one request per minute");
+    Ok(())
+}
+
+#[test]
+fn test_pool_from_settings() -> Result<(), VoyageError> {
+    let config = ConfigLoader::new()
+        .with_env_vars([
+            ("VOYAGE_API_KEYS", "pa-one, pa-two,"),
+            ("VOYAGE_KEY_SELECTION", "least_used"),
+        ])
+        .load()?;
+    let pool = config.key_pool.expect("a key pool");
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.selection(), KeySelection::LeastUsed);
+    assert_eq!(config.api_key.expose(), "pa-one");
+
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_keys(KEYS, KeySelection::Failover)
+        .build()?;
+    let pool = client.config.config.key_pool.expect("a key pool");
+    assert_eq!((pool.len(), pool.selection()), (3, KeySelection::Failover));
+    Ok(())
+}
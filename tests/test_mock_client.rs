@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::search_client::SearchClient;
+use voyageai::client::{MockFailure, MockVoyageClient, RateLimiter, RerankClient};
+use voyageai::traits::llm::{Embedder, Reranker};
+use voyageai::{
+    cosine_similarity, EmbeddingModel, EmbeddingsRequestBuilder, RerankModel, RerankRequest,
+    VoyageConfig, VoyageError,
+};
+
+#[tokio::test]
+async fn test_mock_embeddings_are_deterministic() -> Result<(), VoyageError> {
+    let client = MockVoyageClient::new();
+
+    let first = client.embed("The quick brown fox").await?;
+    let second = MockVoyageClient::new().embed("The quick brown fox").await?;
+
+    assert_eq!(first, second);
+    assert_eq!(first.len(), EmbeddingModel::Voyage3.embedding_dimension());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_embeddings_depend_on_seed() -> Result<(), VoyageError> {
+    let a = MockVoyageClient::with_seed(1).embed("seeded text").await?;
+    let b = MockVoyageClient::with_seed(2).embed("seeded text").await?;
+
+    assert_ne!(a, b);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_embeddings_reflect_word_overlap() -> Result<(), VoyageError> {
+    let client = MockVoyageClient::new();
+    let texts = vec![
+        "rust memory safety".to_string(),
+        "rust memory safety guarantees".to_string(),
+        "baking sourdough bread".to_string(),
+    ];
+
+    let embeddings = Embedder::embed_batch(&client, &texts).await?;
+
+    let close = cosine_similarity(&embeddings[0], &embeddings[1]);
+    let far = cosine_similarity(&embeddings[0], &embeddings[2]);
+    assert!(close > far);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_embeddings_match_model_dimension_and_usage() -> Result<(), VoyageError> {
+    let client = MockVoyageClient::new();
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(["one", "two"])
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+
+    let response = client.embeddings(request).await?;
+
+    assert_eq!(response.data.len(), 2);
+    assert_eq!(response.data[1].index, 1);
+    assert_eq!(response.data[0].embedding.len(), 512);
+    assert_eq!(response.model, "voyage-3-lite");
+    assert!(response.usage.total_tokens > 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_rerank_scores_lexical_overlap() -> Result<(), VoyageError> {
+    let client = MockVoyageClient::new();
+    let request = RerankRequest::new(
+        "capital of France".to_string(),
+        vec![
+            "Berlin is in Germany".to_string(),
+            "Paris is the capital of France".to_string(),
+            "The capital city".to_string(),
+        ],
+        RerankModel::Rerank2,
+        Some(2),
+    )?;
+
+    let response = client.rerank(request).await?;
+
+    assert_eq!(response.data.len(), 2);
+    assert_eq!(response.data[0].index, 1);
+    assert_eq!(response.data[0].relevance_score, 1.0);
+    assert_eq!(response.data[1].index, 2);
+    assert_eq!(response.model, "rerank-2");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mock_scripted_failures_are_returned_in_order() {
+    let client = MockVoyageClient::new();
+    client
+        .fail_next(MockFailure::RateLimited(Duration::from_secs(3)))
+        .fail_next(MockFailure::ServerError("boom".to_string()))
+        .fail_next(MockFailure::Timeout);
+
+    assert!(matches!(
+        client.embed("a").await,
+        Err(VoyageError::RateLimitExceeded { reset_in }) if reset_in == Duration::from_secs(3)
+    ));
+    assert!(matches!(
+        Reranker::rerank_scores(&client, "a", &["a".to_string()]).await,
+        Err(VoyageError::InternalServerError { .. })
+    ));
+    assert!(matches!(client.embed("a").await, Err(VoyageError::Timeout)));
+    assert!(client.embed("a").await.is_ok());
+    assert_eq!(client.calls(), 4);
+}
+
+#[tokio::test]
+async fn test_mock_plugs_into_rerank_client_slot() -> Result<(), VoyageError> {
+    let client = MockVoyageClient::new();
+    let request = RerankRequest::new(
+        "query".to_string(),
+        vec!["query".to_string()],
+        RerankModel::Rerank2,
+        None,
+    )?;
+
+    let response = RerankClient::rerank(&client, &request).await?;
+    assert_eq!(response.data[0].relevance_score, 1.0);
+
+    let embedding_client = EmbeddingClient::new(
+        VoyageConfig::new("test_key".to_string()),
+        Arc::new(RateLimiter::new()),
+    )?;
+    let _search = SearchClient::new(embedding_client, client);
+    Ok(())
+}
@@ -0,0 +1,303 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use common::client_with_config;
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::client::rerank_client::DefaultRerankClient;
+use voyageai::client::retry::{retry_with_exponential_backoff, RetryConfig};
+use voyageai::client::{RateLimiter, RerankClient};
+use voyageai::config::{ConfigLoader, Settings};
+use voyageai::{
+    EmbeddingModel, EmbeddingsRequestBuilder, RerankModel, RerankRequest, VoyageAiClient,
+    VoyageBuilder, VoyageError,
+};
+
+fn rerank_request() -> RerankRequest {
+    RerankRequest::new(
+        "capital of France".to_string(),
+        vec![
+            "Berlin is in Germany".to_string(),
+            "Paris is the capital of France".to_string(),
+        ],
+        RerankModel::Rerank2,
+        None,
+    )
+    .expect("valid rerank request")
+}
+
+#[tokio::test]
+async fn test_embeddings_round_trip_through_http() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.expect_api_key("test_key");
+    let client = client_with_config(server.config("test_key"));
+
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(["first", "second"])
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    let response = client.create_embedding(&request).await?;
+
+    assert_eq!(response.data.len(), 2);
+    assert_eq!(response.data[0].embedding.len(), 512);
+    assert_eq!(response.model, "voyage-3-lite");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/v1/embeddings");
+    assert_eq!(requests[0].header("Authorization"), Some("Bearer test_key"));
+    let body = requests[0].json()?;
+    assert_eq!(body["model"], "voyage-3-lite");
+    assert_eq!(body["input"][1], "second");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rerank_round_trip_through_http() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = DefaultRerankClient::new(server.config("test_key"), Arc::new(RateLimiter::new()))?;
+
+    let response = client.rerank(&rerank_request()).await?;
+
+    assert_eq!(response.data[0].index, 1);
+    assert_eq!(response.model, "rerank-2");
+    assert_eq!(server.requests()[0].path, "/v1/rerank");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wrong_api_key_is_unauthorized() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.expect_api_key("right_key");
+    let client = VoyageAiClient::new_with_config(server.config("wrong_key"))?;
+
+    let result = client.rerank(rerank_request()).await;
+
+    assert!(matches!(result, Err(VoyageError::Unauthorized)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rate_limit_honours_retry_after_and_retries() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.enqueue(MockResponse::status(429).with_retry_after(0));
+    let client = client_with_config(server.config("test_key"));
+
+    let first = client.embed("hello").await;
+    assert!(matches!(
+        first,
+        Err(VoyageError::RateLimitExceeded { reset_in }) if reset_in == Duration::ZERO
+    ));
+
+    server.enqueue(MockResponse::status(429).with_retry_after(0));
+    let embedding =
+        retry_with_exponential_backoff(|| client.embed("hello"), 2, Duration::from_millis(1))
+            .await?;
+    assert!(!embedding.is_empty());
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_server_error_and_malformed_body() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server
+        .enqueue(MockResponse::status(500))
+        .enqueue(MockResponse::malformed());
+    let client = client_with_config(server.config("test_key"));
+
+    assert!(matches!(
+        client.embed("hello").await,
+        Err(VoyageError::InternalServerError { .. })
+    ));
+    assert!(matches!(
+        client.embed("hello").await,
+        Err(VoyageError::JsonError(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_latency_is_applied() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.set_latency(Duration::from_millis(150));
+    let client = client_with_config(server.config("test_key"));
+
+    let started = Instant::now();
+    client.embed("slow").await?;
+
+    assert!(started.elapsed() >= Duration::from_millis(150));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_per_request_assertions() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.assert_each(|request| {
+        let body = request.json().map_err(|e| e.to_string())?;
+        if body.get("input_type").is_some() {
+            Ok(())
+        } else {
+            Err("input_type was not sent".to_string())
+        }
+    });
+    let client = client_with_config(server.config("test_key"));
+
+    let result = client.embed("no input type").await;
+
+    assert!(matches!(result, Err(VoyageError::BadRequest { .. })));
+    assert_eq!(server.assertion_failures(), vec!["input_type was not sent"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dedupe_sends_unique_inputs_only() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client_with_config(server.config("test_key").with_dedupe_inputs(true));
+    let texts: Vec<String> = ["header", "body", "header"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(texts.clone())
+        .model(EmbeddingModel::Voyage3)
+        .build()?;
+    let response = client.create_embedding(&request).await?;
+
+    let sent = server.requests()[0].json()?;
+    assert_eq!(sent["input"].as_array().map(Vec::len), Some(2));
+    assert_eq!(response.data.len(), 3);
+    assert_eq!(response.data[2].index, 2);
+    assert_eq!(response.data[0].embedding, response.data[2].embedding);
+    let stats = response.dedupe.expect("dedupe stats");
+    assert_eq!(stats.inputs_saved, 1);
+    assert!(stats.tokens_saved > 0);
+    Ok(())
+}
+
+/// Dedupe enabled through the config layers applies to the public client end to end.
+#[tokio::test]
+async fn test_client_dedupes_batches_end_to_end() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_settings(Settings {
+            api_key: Some("test_key".into()),
+            base_url: Some(server.base_url()),
+            dedupe_inputs: Some(true),
+            ..Settings::default()
+        })
+        .build()?;
+    let texts: Vec<String> = ["a", "b", "a", "c", "b", "a"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(texts.clone())
+        .model(EmbeddingModel::default())
+        .build()?;
+    let response = client.embeddings(request).await?;
+    let batch = client.embed_batch(texts).await?;
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for sent in &requests {
+        assert_eq!(sent.json()?["input"], serde_json::json!(["a", "b", "c"]));
+    }
+    let stats = response.dedupe.expect("dedupe stats");
+    assert_eq!(stats.inputs_saved, 3);
+    assert!(stats.tokens_saved > 0);
+    let embeddings: Vec<_> = response.data.iter().map(|d| &d.embedding).collect();
+    assert_eq!(embeddings.len(), 6);
+    assert_eq!(embeddings[0], embeddings[2]);
+    assert_eq!(embeddings[0], embeddings[5]);
+    assert_eq!(embeddings[1], embeddings[4]);
+    assert_ne!(embeddings[0], embeddings[1]);
+    assert_eq!(batch[3], *embeddings[3]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_without_dedupe_sends_every_input() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = VoyageAiClient::new_with_config(server.config("test_key"))?;
+
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(["a", "a"])
+        .model(EmbeddingModel::Voyage3Lite)
+        .build()?;
+    let response = client.embeddings(request).await?;
+
+    assert_eq!(
+        server.requests()[0].json()?["input"],
+        serde_json::json!(["a", "a"])
+    );
+    assert!(response.dedupe.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transient_failures_are_retried() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server
+        .enqueue(MockResponse::status(503))
+        .enqueue(MockResponse::status(429).with_retry_after(0));
+    let retry = RetryConfig {
+        max_retries: 2,
+        initial_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+    };
+    let client = client_with_config(server.config("test_key").with_retry(retry));
+
+    let embedding = client.embed("eventually").await?;
+
+    assert_eq!(embedding.len(), 1024);
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retries_stop_at_limit_and_skip_client_errors() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server
+        .enqueue(MockResponse::status(500))
+        .enqueue(MockResponse::status(500))
+        .enqueue(MockResponse::status(400));
+    let retry = RetryConfig {
+        max_retries: 1,
+        initial_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+    };
+    let client = client_with_config(server.config("test_key").with_retry(retry));
+
+    let result = client.embed("failing").await;
+    assert!(matches!(
+        result,
+        Err(VoyageError::InternalServerError { .. })
+    ));
+    assert_eq!(server.requests().len(), 2);
+
+    let result = client.embed("bad").await;
+    assert!(matches!(result, Err(VoyageError::BadRequest { .. })));
+    assert_eq!(server.requests().len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timeout_maps_to_timeout_error() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.set_latency(Duration::from_millis(500));
+    let client = client_with_config(
+        server
+            .config("test_key")
+            .with_timeout(Duration::from_millis(50)),
+    );
+
+    let result = client.embed("slow").await;
+
+    assert!(matches!(result, Err(VoyageError::Timeout)));
+    Ok(())
+}
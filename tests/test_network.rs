@@ -0,0 +1,167 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use voyageai::backends::{OpenAiCompatibleBackend, OpenAiCompatibleConfig};
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::RateLimiter;
+use voyageai::config::{ConfigLoader, NetworkConfig};
+use voyageai::{VoyageBuilder, VoyageConfig, VoyageError};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("voyageai-network-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let path = dir.join(format!("{}.toml", name));
+    std::fs::write(&path, contents).expect("write config file");
+    path
+}
+
+#[test]
+fn test_network_settings_from_config_file() -> Result<(), VoyageError> {
+    let path = config_file(
+        "network",
+        &format!(
+            r#"
+[default]
+api_key = "file-key"
+proxy = "http://proxy.corp.example:3128"
+no_proxy = ["localhost", "internal.example"]
+system_proxy = false
+ca_certs = [{ca:?}]
+client_cert = {cert:?}
+client_key = {key:?}
+"#,
+            ca = fixture("ca.pem"),
+            cert = fixture("client.pem"),
+            key = fixture("client.key"),
+        ),
+    );
+
+    let config = ConfigLoader::new().with_file(&path).load()?;
+    let network = &config.network;
+    assert_eq!(
+        network.proxy.as_ref().map(|url| url.as_str()),
+        Some("http://proxy.corp.example:3128/")
+    );
+    assert_eq!(network.no_proxy, ["localhost", "internal.example"]);
+    assert!(network.disable_system_proxy);
+    assert_eq!(network.root_certificates.len(), 1);
+    assert!(network.identity.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_network_settings_from_env() -> Result<(), VoyageError> {
+    let config = ConfigLoader::new()
+        .with_env_vars([
+            ("VOYAGE_API_KEY", "env-key"),
+            ("VOYAGE_PROXY", "http://proxy.corp.example:3128"),
+            ("VOYAGE_NO_PROXY", "localhost, .internal.example"),
+            ("VOYAGE_CA_CERTS", fixture("ca.pem").to_str().unwrap()),
+        ])
+        .load()?;
+
+    assert!(config.network.proxy.is_some());
+    assert_eq!(config.network.no_proxy, ["localhost", ".internal.example"]);
+    assert!(!config.network.disable_system_proxy);
+    assert_eq!(config.network.root_certificates.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_network_settings_are_config_errors() {
+    let result = NetworkConfig::default().with_proxy("not a url");
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("not a url")));
+
+    let result = NetworkConfig::default().with_root_certificate_file("/nonexistent/ca.pem");
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("/nonexistent")));
+
+    let result = NetworkConfig::default().with_root_certificate_file(fixture("client.key"));
+    assert!(matches!(result, Err(VoyageError::ConfigError(_))));
+
+    let result = NetworkConfig::default()
+        .with_client_identity_files(fixture("client.pem"), fixture("ca.pem"));
+    assert!(matches!(result, Err(VoyageError::ConfigError(_))));
+
+    let result = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("key")
+        .with_settings(voyageai::config::Settings {
+            client_cert: Some(fixture("client.pem")),
+            ..Default::default()
+        })
+        .build();
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("client_key")));
+}
+
+#[test]
+fn test_unusable_tls_settings_fail_the_build() {
+    let result = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("key")
+        .with_ca_certs(["/nonexistent/ca.pem"])
+        .build();
+    assert!(matches!(result, Err(VoyageError::ConfigError(m)) if m.contains("/nonexistent")));
+
+    // The files parse on their own, but the key does not belong to the certificate,
+    // which only the HTTP client notices when it is built.
+    let result = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("key")
+        .with_client_identity(fixture("client.pem"), fixture("other.key"))
+        .build();
+    assert!(
+        matches!(result, Err(VoyageError::ConfigError(ref m)) if m.contains("HTTP client")),
+        "{:?}",
+        result.err()
+    );
+
+    let network = NetworkConfig::default()
+        .with_client_identity_files(fixture("client.pem"), fixture("other.key"))
+        .expect("files parse");
+    let config = VoyageConfig::new("key").with_network(network);
+    let result = EmbeddingClient::new(config.clone(), Arc::new(RateLimiter::new()));
+    assert!(matches!(result, Err(VoyageError::ConfigError(_))));
+    let backend = OpenAiCompatibleConfig::new("http://localhost:1/v1", "model", 8);
+    let result = OpenAiCompatibleBackend::with_settings_from(backend, &config);
+    assert!(matches!(result, Err(VoyageError::ConfigError(_))));
+}
+
+#[tokio::test]
+async fn test_requests_go_through_proxy() -> Result<(), VoyageError> {
+    let proxy = MockServer::start().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("test_key")
+        .with_base_url("http://api.voyage.invalid/v1")
+        .with_proxy(proxy.base_url())
+        .without_system_proxy()
+        .build()?;
+
+    let embedding = client.embed_text("hello").await?;
+
+    assert!(!embedding.is_empty());
+    let requests = proxy.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1/embeddings");
+    assert_eq!(requests[0].header("host"), Some("api.voyage.invalid"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_proxy_hosts_bypass_proxy() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = VoyageBuilder::with_loader(ConfigLoader::new())
+        .with_api_key("test_key")
+        .with_base_url(server.base_url())
+        .with_proxy("http://127.0.0.1:9")
+        .with_no_proxy(["127.0.0.1"])
+        .build()?;
+
+    client.embed_text("hello").await?;
+
+    assert_eq!(server.requests().len(), 1);
+    Ok(())
+}
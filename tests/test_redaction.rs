@@ -0,0 +1,97 @@
+use std::sync::{Arc, Mutex};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::rerank_client::DefaultRerankClient;
+use voyageai::client::{RateLimiter, RerankClient};
+use voyageai::{ApiKey, BodyLogPolicy, RerankModel, RerankRequest, VoyageConfig, VoyageError};
+
+struct CapturingLogger {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        self.lines.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn test_api_key_is_redacted_in_debug_and_display() {
+    let key = ApiKey::new("pa-super-secret");
+
+    assert_eq!(key.to_string(), "[REDACTED]");
+    assert_eq!(format!("{:?}", key), "ApiKey([REDACTED])");
+    assert_eq!(key.expose(), "pa-super-secret");
+    assert_eq!(ApiKey::default().to_string(), "<unset>");
+
+    let config = VoyageConfig::new("pa-super-secret");
+    assert_eq!(config.api_key(), "pa-super-secret");
+    assert!(!format!("{:?}", config).contains("pa-super-secret"));
+}
+
+#[test]
+fn test_body_log_policy_rendering() {
+    let body = r#"{"input":["héllo wörld"]}"#;
+
+    assert_eq!(BodyLogPolicy::Off.render(body), None);
+    assert_eq!(
+        BodyLogPolicy::LengthsOnly.render(body).as_deref(),
+        Some("<27 bytes>")
+    );
+    assert_eq!(
+        BodyLogPolicy::Truncated(12).render(body).as_deref(),
+        Some(r#"{"input":["h... <13 more chars>"#)
+    );
+    assert_eq!(
+        BodyLogPolicy::Truncated(100).render(body).as_deref(),
+        Some(body)
+    );
+    assert_eq!(BodyLogPolicy::Full.render(body).as_deref(), Some(body));
+    assert_eq!(BodyLogPolicy::default(), BodyLogPolicy::LengthsOnly);
+}
+
+#[tokio::test]
+async fn test_logs_carry_no_key_or_text_by_default() -> Result<(), VoyageError> {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    log::set_boxed_logger(Box::new(CapturingLogger {
+        lines: lines.clone(),
+    }))
+    .expect("logger installed once");
+    log::set_max_level(LevelFilter::Debug);
+
+    let server = MockServer::start().await?;
+    let client = DefaultRerankClient::new(
+        server.config("pa-super-secret"),
+        Arc::new(RateLimiter::new()),
+    )?;
+    let request = RerankRequest::new(
+        "confidential query".to_string(),
+        vec!["private document".to_string()],
+        RerankModel::Rerank2,
+        None,
+    )?;
+    client.rerank(&request).await?;
+
+    let lines = lines.lock().unwrap();
+    assert!(lines.iter().any(|line| line.starts_with("Request body: <")));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("Response 200 OK body: <")));
+    for line in lines.iter() {
+        assert!(!line.contains("pa-super-secret"), "key leaked: {}", line);
+        assert!(!line.contains("confidential"), "query leaked: {}", line);
+        assert!(
+            !line.contains("private document"),
+            "document leaked: {}",
+            line
+        );
+    }
+    Ok(())
+}
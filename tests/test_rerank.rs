@@ -2,7 +2,7 @@ use voyageai::{RerankModel, RerankRequest, VoyageAiClient};
 
 #[tokio::test]
 async fn test_rerank() -> Result<(), Box<dyn std::error::Error>> {
-    let client = VoyageAiClient::new();
+    let client = VoyageAiClient::new()?;
 
     let query = "What is the capital of France?";
     let documents = [
@@ -47,7 +47,7 @@ async fn test_rerank() -> Result<(), Box<dyn std::error::Error>> {
 #[tokio::test]
 async fn test_rerank_invalid_input() -> Result<(), Box<dyn std::error::Error>> {
     let _api_key = std::env::var("VOYAGE_API_KEY").expect("VOYAGE_API_KEY must be set");
-    let _client = VoyageAiClient::new();
+    let _client = VoyageAiClient::new()?;
 
     let result = RerankRequest::new("".to_string(), vec![], RerankModel::Rerank2, Some(2));
 
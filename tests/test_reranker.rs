@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::rerank_client::DefaultRerankClient;
+use voyageai::client::{Bm25Reranker, MockVoyageClient, RateLimiter};
+use voyageai::traits::llm::{RerankOptions, Reranker};
+use voyageai::{RerankModel, VoyageError};
+
+fn documents() -> Vec<String> {
+    vec![
+        "python is a snake".to_string(),
+        "rust is a language and rust is fast".to_string(),
+        "the rust language book".to_string(),
+    ]
+}
+
+/// Every document index appears once and results are sorted by score.
+fn assert_well_formed(results: &[voyageai::models::rerank::RerankResult], count: usize) {
+    let mut indices: Vec<_> = results.iter().map(|r| r.index).collect();
+    indices.sort_unstable();
+    assert_eq!(indices, (0..count).collect::<Vec<_>>());
+    assert!(results
+        .windows(2)
+        .all(|w| w[0].relevance_score >= w[1].relevance_score));
+}
+
+#[tokio::test]
+async fn test_api_reranker_carries_indices_and_options() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = DefaultRerankClient::new(server.config("test_key"), Arc::new(RateLimiter::new()))?;
+    let documents = documents();
+
+    let all = Reranker::rerank(&client, "rust language", &documents, &Default::default()).await?;
+    assert_well_formed(&all, 3);
+    assert_eq!(all[0].index, 1);
+    assert!(all.iter().all(|r| r.document.is_none()));
+
+    let options = RerankOptions::default()
+        .with_top_k(1)
+        .with_model(RerankModel::Rerank2Lite)
+        .with_documents();
+    let top = Reranker::rerank(&client, "python", &documents, &options).await?;
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].index, 0);
+    assert_eq!(top[0].document.as_deref(), Some("python is a snake"));
+
+    let sent = server.requests()[1].json().unwrap();
+    assert_eq!(sent["model"], "rerank-2-lite");
+    assert_eq!(sent["top_k"], 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scores_are_in_input_order() -> Result<(), VoyageError> {
+    let mock = MockVoyageClient::new();
+    let documents = documents();
+
+    let scores = mock.rerank_scores("rust language", &documents).await?;
+
+    assert_eq!(scores.len(), 3);
+    for (score, document) in scores.iter().zip(&documents) {
+        assert_eq!(
+            *score,
+            MockVoyageClient::relevance_score("rust language", document)
+        );
+    }
+    assert!(Reranker::rerank(&mock, "q", &[], &Default::default())
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bm25_reranker() -> Result<(), VoyageError> {
+    let bm25 = Bm25Reranker::new();
+    let documents = documents();
+
+    let results = bm25
+        .rerank("Rust language", &documents, &RerankOptions::default())
+        .await?;
+    assert_well_formed(&results, 3);
+    // Both rust documents match both terms; the shorter one ranks higher despite the
+    // other repeating "rust".
+    assert_eq!(results[0].index, 2);
+    assert_eq!(results[1].index, 1);
+    assert_eq!(results[2].relevance_score, 0.0);
+
+    let scores = bm25.scores("Rust language", &documents);
+    assert_eq!(results[0].relevance_score, scores[2]);
+    assert_eq!(bm25.scores("anything", &[]), Vec::<f64>::new());
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::client::rerank_client::DefaultRerankClient;
+use voyageai::client::retry::RetryConfig;
+use voyageai::client::{RateLimiter, RerankClient};
+use voyageai::models::embeddings::EmbeddingsRequest;
+use voyageai::models::ResponseMetadata;
+use voyageai::{EmbeddingModel, EmbeddingsInput, RerankModel, RerankRequest, VoyageError};
+
+fn request(text: &str) -> EmbeddingsRequest {
+    EmbeddingsRequest {
+        input: EmbeddingsInput::Single(text.to_string()),
+        model: EmbeddingModel::Voyage3Lite,
+        input_type: None,
+        truncation: None,
+        encoding_format: None,
+    }
+}
+
+#[tokio::test]
+async fn test_embeddings_carry_metadata() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.set_latency(Duration::from_millis(20));
+    let client = EmbeddingClient::new(server.config("test_key"), Arc::new(RateLimiter::new()))?;
+
+    let response = client.create_embedding(&request("hello")).await?;
+
+    let metadata = &response.metadata;
+    assert_eq!(metadata.request_id.as_deref(), Some("mock-req-1"));
+    assert!(metadata.latency >= Duration::from_millis(20));
+    assert_eq!(metadata.retries, 0);
+    assert_eq!(metadata.rate_limit_wait, Duration::ZERO);
+    assert_eq!(metadata.rate_limit.remaining_requests, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metadata_counts_retries() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server
+        .enqueue(MockResponse::status(503))
+        .enqueue(MockResponse::status(500));
+    let retry = RetryConfig {
+        max_retries: 3,
+        initial_delay: Duration::from_millis(5),
+        max_delay: Duration::from_millis(10),
+    };
+    let client = EmbeddingClient::new(
+        server.config("test_key").with_retry(retry),
+        Arc::new(RateLimiter::new()),
+    )?;
+
+    let response = client.create_embedding(&request("hello")).await?;
+
+    assert_eq!(response.metadata.retries, 2);
+    assert_eq!(response.metadata.request_id.as_deref(), Some("mock-req-3"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rerank_reads_rate_limit_headers() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let body = json!({
+        "object": "list",
+        "data": [{ "relevance_score": 0.9, "index": 0 }],
+        "model": "rerank-2",
+        "usage": { "total_tokens": 4 },
+    });
+    server.enqueue(
+        MockResponse::json(200, &body)
+            .with_header("x-request-id", "req_abc123")
+            .with_header("x-ratelimit-limit-requests", "300")
+            .with_header("x-ratelimit-remaining-requests", "299")
+            .with_header("x-ratelimit-remaining-tokens", "999996")
+            .with_header("x-ratelimit-reset-tokens", "1m30s"),
+    );
+    let client = DefaultRerankClient::new(server.config("test_key"), Arc::new(RateLimiter::new()))?;
+    let request = RerankRequest::new(
+        "query".to_string(),
+        vec!["document".to_string()],
+        RerankModel::Rerank2,
+        None,
+    )?;
+
+    let response = client.rerank(&request).await?;
+
+    let metadata = &response.metadata;
+    assert_eq!(metadata.request_id.as_deref(), Some("req_abc123"));
+    assert_eq!(metadata.rate_limit.limit_requests, Some(300));
+    assert_eq!(metadata.rate_limit.remaining_requests, Some(299));
+    assert_eq!(metadata.rate_limit.remaining_tokens, Some(999_996));
+    assert_eq!(
+        metadata.rate_limit.reset_tokens,
+        Some(Duration::from_secs(90))
+    );
+    assert_eq!(metadata.rate_limit.reset_requests, None);
+    Ok(())
+}
+
+#[test]
+fn test_reset_header_formats() {
+    let reset = |value: &'static str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static(value),
+        );
+        let mut metadata = ResponseMetadata::default();
+        metadata.read_headers(&headers);
+        metadata.rate_limit.reset_requests
+    };
+
+    assert_eq!(reset("12"), Some(Duration::from_secs(12)));
+    assert_eq!(reset("0.5"), Some(Duration::from_millis(500)));
+    assert_eq!(reset("250ms"), Some(Duration::from_millis(250)));
+    assert_eq!(reset("1h2m3s"), Some(Duration::from_secs(3723)));
+    assert_eq!(reset("1.5s"), Some(Duration::from_millis(1500)));
+    assert_eq!(reset("soon"), None);
+}
@@ -5,16 +5,16 @@ use voyageai::traits::llm::Embedder;
 async fn test_search_client_integration() -> Result<(), Box<dyn std::error::Error>> {
     let api_key = std::env::var("VOYAGE_API_KEY").expect("VOYAGE_API_KEY must be set");
     let config = VoyageConfig::new(api_key);
-    let client = VoyageAiClient::new_with_config(config);
+    let client = VoyageAiClient::new_with_config(config)?;
 
     // Test direct embedding
     let text = "Test document";
     let embedding = client.embed_text(text).await.unwrap();
-    assert!(!embedding[0].is_empty());
+    assert!(!embedding.is_empty());
 
     // Test batch embedding
     let texts = vec!["test document 1".to_string(), "test document 2".to_string()];
-    let embeddings = client.embed_batch(&texts).await.unwrap();
+    let embeddings = Embedder::embed_batch(&client, &texts).await.unwrap();
     assert_eq!(embeddings.len(), 2);
     assert!(!embeddings[0].is_empty());
     assert!(!embeddings[1].is_empty());
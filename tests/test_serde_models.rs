@@ -0,0 +1,184 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use voyageai::builder::search::{SearchRequest, SearchRequestBuilder};
+use voyageai::models::embeddings::{
+    DedupeStats, EmbeddingData, EmbeddingsRequest, EmbeddingsResponse, EncodingFormat, SplitInput,
+    Usage,
+};
+use voyageai::models::rerank::{self, RerankResult};
+use voyageai::models::{RateLimitHeaders, ResponseMetadata};
+use voyageai::{
+    EmbeddingModel, EmbeddingsInput, InputType, RerankModel, RerankRequest, RerankResponse,
+    SearchModel, SearchType, StableHash, VoyageError,
+};
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+fn embeddings_request(texts: &[&str]) -> EmbeddingsRequest {
+    EmbeddingsRequest {
+        input: EmbeddingsInput::Multiple(texts.iter().map(|t| t.to_string()).collect()),
+        model: EmbeddingModel::Voyage3,
+        input_type: Some(InputType::Document),
+        truncation: None,
+        encoding_format: Some(EncodingFormat::Float),
+    }
+}
+
+fn rerank_request(query: &str) -> RerankRequest {
+    RerankRequest::new(
+        query.to_string(),
+        vec!["a".to_string(), "b".to_string()],
+        RerankModel::Rerank2Lite,
+        Some(1),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_requests_round_trip() {
+    let request = embeddings_request(&["one", "two"]);
+    assert_eq!(round_trip(&request), request);
+    let single = EmbeddingsRequest {
+        input: EmbeddingsInput::Single("one".to_string()),
+        ..request.clone()
+    };
+    assert_eq!(round_trip(&single), single);
+
+    let rerank = rerank_request("query");
+    assert_eq!(round_trip(&rerank), rerank.clone());
+}
+
+#[test]
+fn test_rerank_request_deserialization_validates() {
+    let json = r#"{"query": "q", "documents": [], "model": "rerank-2"}"#;
+    let error = serde_json::from_str::<RerankRequest>(json).unwrap_err();
+    assert!(error.to_string().contains("documents cannot be empty"));
+
+    let json = r#"{"query": "q", "documents": ["d"], "model": "rerank-2"}"#;
+    let request: RerankRequest = serde_json::from_str(json).unwrap();
+    assert_eq!(request.top_k, None);
+}
+
+#[test]
+fn test_responses_round_trip() {
+    let metadata = ResponseMetadata {
+        request_id: Some("req_1".to_string()),
+        retries: 2,
+        rate_limit: RateLimitHeaders {
+            remaining_tokens: Some(10),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let response = EmbeddingsResponse {
+        object: "list".to_string(),
+        data: vec![EmbeddingData {
+            object: "embedding".to_string(),
+            embedding: vec![0.25, -0.5],
+            index: 0,
+        }],
+        model: "voyage-3".to_string(),
+        usage: Usage { total_tokens: 7 },
+        dedupe: Some(DedupeStats {
+            inputs_saved: 1,
+            tokens_saved: 3,
+        }),
+        split: vec![SplitInput {
+            index: 0,
+            windows: 2,
+        }],
+        served_by: Some("#1 …9f2c".to_string()),
+        metadata,
+    };
+    assert_eq!(round_trip(&response), response);
+
+    let rerank = RerankResponse {
+        object: "list".to_string(),
+        data: vec![RerankResult {
+            relevance_score: 0.75,
+            index: 1,
+            document: None,
+        }],
+        model: "rerank-2".to_string(),
+        usage: rerank::Usage { total_tokens: 4 },
+        served_by: None,
+        metadata: ResponseMetadata::default(),
+    };
+    let json = serde_json::to_value(&rerank).unwrap();
+    assert!(json.get("metadata").is_none() && json.get("served_by").is_none());
+    assert_eq!(round_trip(&rerank), rerank);
+}
+
+#[test]
+fn test_stable_hash() -> Result<(), VoyageError> {
+    let request = embeddings_request(&["one", "two"]);
+    assert_eq!(request.stable_hash()?, request.clone().stable_hash()?);
+    assert_eq!(request.stable_key()?.len(), 16);
+    assert_ne!(
+        request.stable_hash()?,
+        embeddings_request(&["two", "one"]).stable_hash()?
+    );
+    assert_ne!(
+        request.stable_hash()?,
+        EmbeddingsRequest {
+            input_type: Some(InputType::Query),
+            ..request.clone()
+        }
+        .stable_hash()?
+    );
+    // Pinned so that changes to the hash, which would invalidate persisted keys, are
+    // noticed.
+    assert_eq!(
+        embeddings_request(&["one"]).stable_key()?,
+        "40f9ec2bdc22fe7c"
+    );
+
+    assert_eq!(
+        rerank_request("q").stable_hash()?,
+        rerank_request("q").stable_hash()?
+    );
+    assert_ne!(
+        rerank_request("q").stable_hash()?,
+        rerank_request("r").stable_hash()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_stable_hash_rejects_requests_that_do_not_serialize() {
+    let too_many = |last: &str| {
+        let mut request = rerank_request("q");
+        request.documents = (0..100).map(|i| i.to_string()).collect();
+        request.documents.push(last.to_string());
+        request
+    };
+
+    assert!(matches!(
+        too_many("a").stable_hash(),
+        Err(VoyageError::JsonError(_))
+    ));
+    assert!(too_many("b").stable_key().is_err());
+}
+
+#[test]
+fn test_search_request_equality_and_round_trip() {
+    let request = SearchRequestBuilder::new()
+        .query("rust")
+        .documents(["a".to_string(), "b".to_string()])
+        .model(SearchModel::default())
+        .search_type(SearchType::Similarity)
+        .build()
+        .unwrap();
+
+    assert_eq!(round_trip(&request), request);
+    assert_ne!(
+        SearchRequest {
+            top_k: Some(1),
+            ..request.clone()
+        },
+        request
+    );
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::{client_with_config, config};
+use voyageai::client::embeddings_client::EmbeddingClient;
+use voyageai::client::mock_server::MockServer;
+use voyageai::client::splitting::{pool, split_text};
+use voyageai::client::{Pooling, SplitConfig};
+use voyageai::config::ConfigLoader;
+use voyageai::models::embeddings::{EmbeddingsRequest, SplitInput};
+use voyageai::{EmbeddingModel, EmbeddingsInput, EmbeddingsRequestBuilder, VoyageError};
+
+fn client(server: &MockServer, split: SplitConfig) -> EmbeddingClient {
+    client_with_config(config(server).with_split_long_inputs(split))
+}
+
+fn request(texts: &[String]) -> EmbeddingsRequest {
+    EmbeddingsRequest {
+        input: EmbeddingsInput::Multiple(texts.to_vec()),
+        model: EmbeddingModel::Voyage3Lite,
+        input_type: None,
+        truncation: None,
+        encoding_format: None,
+    }
+}
+
+#[test]
+fn test_split_text_windows_overlap() {
+    let text = "0123456789".repeat(10);
+
+    let windows = split_text(&text, 10, 2);
+
+    assert!(windows.iter().all(|w| w.len() <= 40));
+    assert_eq!(windows[0], text[..40]);
+    assert_eq!(windows[1], text[32..72]);
+    assert_eq!(windows.last().unwrap(), &text[64..]);
+    assert_eq!(split_text("short", 10, 2), ["short"]);
+}
+
+#[test]
+fn test_split_text_prefers_whitespace_and_char_boundaries() {
+    let text = format!("{} {}", "a".repeat(35), "b".repeat(30));
+    let windows = split_text(&text, 10, 0);
+    assert_eq!(windows[0], "a".repeat(35));
+
+    let accented = "é".repeat(50);
+    for window in split_text(&accented, 5, 1) {
+        assert!(window.chars().all(|c| c == 'é'));
+    }
+}
+
+#[test]
+fn test_pooling() {
+    let embeddings = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+    let mean = pool(&embeddings, Pooling::Mean);
+    assert!((mean[0] - mean[1]).abs() < 1e-6);
+    assert!((mean[0] * mean[0] + mean[1] * mean[1] - 1.0).abs() < 1e-6);
+    assert_eq!(pool(&embeddings, Pooling::First), vec![1.0, 0.0]);
+    let max = pool(&[vec![0.6, -1.0], vec![-1.0, 0.8]], Pooling::Max);
+    assert!((max[0] - 0.6).abs() < 1e-6 && (max[1] - 0.8).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_long_inputs_are_split_and_pooled() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let split = SplitConfig::default()
+        .with_window_tokens(10)
+        .with_overlap_tokens(2);
+    let client = client(&server, split);
+    let long = "0123456789".repeat(10);
+    let texts = vec!["short".to_string(), long.clone()];
+
+    let response = client.create_embedding(&request(&texts)).await?;
+
+    assert_eq!(response.data.len(), 2);
+    assert_eq!(
+        response.split,
+        [SplitInput {
+            index: 1,
+            windows: 3
+        }]
+    );
+    let sent = server.requests()[0].json().unwrap();
+    assert_eq!(sent["input"].as_array().unwrap().len(), 4);
+
+    assert_eq!(response.data[0].embedding, client.embed("short").await?);
+    let mut windows = Vec::new();
+    for window in split_text(&long, 10, 2) {
+        windows.push(client.embed(&window).await?);
+    }
+    let expected = pool(&windows, Pooling::Mean);
+    assert_eq!(response.data[1].embedding, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_short_inputs_are_sent_unchanged() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server, SplitConfig::default());
+    let texts = vec!["one".to_string(), "two".to_string()];
+
+    let response = client.create_embedding(&request(&texts)).await?;
+
+    assert!(response.split.is_empty());
+    let sent = server.requests()[0].json().unwrap();
+    assert_eq!(sent["input"], serde_json::json!(["one", "two"]));
+    Ok(())
+}
+
+#[test]
+fn test_split_settings_from_env() -> Result<(), VoyageError> {
+    let config = ConfigLoader::new()
+        .with_env_vars([
+            ("VOYAGE_API_KEY", "key"),
+            ("VOYAGE_SPLIT_POOLING", "max"),
+            ("VOYAGE_SPLIT_OVERLAP_TOKENS", "50"),
+        ])
+        .load()?;
+    let split = config.split_long_inputs.expect("splitting enabled");
+    assert_eq!(split.pooling, Pooling::Max);
+    assert_eq!(split.overlap_tokens, 50);
+    assert_eq!(split.window_for(EmbeddingModel::VoyageLaw2), 16_000);
+
+    let disabled = ConfigLoader::new()
+        .with_env_vars([
+            ("VOYAGE_API_KEY", "key"),
+            ("VOYAGE_SPLIT_LONG_INPUTS", "false"),
+            ("VOYAGE_SPLIT_POOLING", "max"),
+        ])
+        .load()?;
+    assert!(disabled.split_long_inputs.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_builder_accepts_long_inputs_for_splitting() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = client(&server, SplitConfig::default());
+    // About 40k tokens, over voyage-3-lite's 32k context length.
+    let long = "word ".repeat(32_000);
+    let builder = EmbeddingsRequestBuilder::new()
+        .documents([long, "short".to_string()])
+        .model(EmbeddingModel::Voyage3Lite)
+        .truncation(false);
+
+    let rejected = builder.clone().build().map_err(VoyageError::from);
+    assert!(matches!(
+        rejected,
+        Err(VoyageError::InputTooLong { index: 0, .. })
+    ));
+
+    let request = builder.split_long_inputs(true).build()?;
+    let response = client.create_embedding(&request).await?;
+
+    assert_eq!(response.data.len(), 2);
+    assert_eq!(
+        response.split,
+        [SplitInput {
+            index: 0,
+            windows: 2
+        }]
+    );
+    let bodies: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|r| r.json().unwrap())
+        .collect();
+    assert!(bodies.iter().all(|body| body["truncation"] == false));
+    Ok(())
+}
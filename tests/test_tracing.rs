@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use voyageai::client::mock_server::{MockResponse, MockServer};
+use voyageai::{RerankModel, RerankRequest, VoyageAiClient, VoyageError};
+
+#[derive(Debug, Clone, Default)]
+struct CapturedSpan {
+    name: String,
+    fields: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct CapturingSubscriber {
+    spans: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for CapturingSubscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut span = CapturedSpan {
+            name: attributes.metadata().name().to_string(),
+            ..Default::default()
+        };
+        attributes.record(&mut FieldVisitor(&mut span.fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(span);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, id: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let span = &mut spans[id.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, _: &Event<'_>) {}
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+fn capture() -> (
+    Arc<Mutex<Vec<CapturedSpan>>>,
+    tracing::subscriber::DefaultGuard,
+) {
+    let subscriber = CapturingSubscriber::default();
+    let spans = subscriber.spans.clone();
+    (spans, tracing::subscriber::set_default(subscriber))
+}
+
+fn find(spans: &Arc<Mutex<Vec<CapturedSpan>>>, name: &str) -> CapturedSpan {
+    spans
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|span| span.name == name)
+        .cloned()
+        .unwrap_or_else(|| panic!("no span named {}", name))
+}
+
+#[tokio::test]
+async fn test_embeddings_span_carries_fields() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    let client = VoyageAiClient::new_with_config(server.config("test_key"))?;
+    let (spans, _guard) = capture();
+
+    client
+        .embed_batch(vec!["one".to_string(), "two".to_string()])
+        .await?;
+
+    let span = find(&spans, "voyage.embeddings");
+    assert_eq!(span.fields["model"], "\"voyage-3\"");
+    assert_eq!(span.fields["input_count"], "2");
+    assert!(span.fields.contains_key("estimated_tokens"));
+    assert!(span.fields.contains_key("actual_tokens"));
+    assert_eq!(span.fields["status"], "200");
+    assert!(span.fields.contains_key("latency_ms"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rerank_span_records_errors() -> Result<(), VoyageError> {
+    let server = MockServer::start().await?;
+    server.enqueue(MockResponse::status(500));
+    let client = VoyageAiClient::new_with_config(server.config("test_key"))?;
+    let (spans, _guard) = capture();
+
+    let request = RerankRequest::new(
+        "query".to_string(),
+        vec!["doc".to_string()],
+        RerankModel::Rerank2Lite,
+        None,
+    )?;
+    let result = client.rerank(request).await;
+
+    assert!(result.is_err());
+    let span = find(&spans, "voyage.rerank");
+    assert_eq!(span.fields["model"], "\"rerank-2-lite\"");
+    assert_eq!(span.fields["status"], "500");
+    assert!(span.fields["error"].contains("Internal Server Error"));
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use voyageai::builder::embeddings::EmbeddingsRequestBuilder;
+use voyageai::errors::VoyageBuilderError;
+use voyageai::models::rerank::ValidationError;
+use voyageai::{EmbeddingModel, RerankModel, RerankRequest, VoyageError};
+
+fn build(texts: Vec<String>, model: EmbeddingModel) -> Result<(), VoyageError> {
+    EmbeddingsRequestBuilder::new()
+        .documents(texts)
+        .model(model)
+        .build()
+        .map(|_| ())
+        .map_err(VoyageError::from)
+}
+
+#[test]
+fn test_embeddings_input_count() {
+    let too_many = vec!["text".to_string(); 129];
+    assert!(matches!(
+        build(too_many, EmbeddingModel::Voyage3),
+        Err(VoyageError::InputListTooLong)
+    ));
+    assert!(build(vec!["text".to_string(); 128], EmbeddingModel::Voyage3).is_ok());
+
+    let none = EmbeddingsRequestBuilder::new()
+        .documents(Vec::<String>::new())
+        .model(EmbeddingModel::Voyage3)
+        .build();
+    assert!(matches!(none, Err(VoyageBuilderError::MissingInput)));
+}
+
+#[test]
+fn test_embeddings_empty_string() {
+    let texts = vec!["a".to_string(), String::new()];
+    assert!(matches!(
+        build(texts, EmbeddingModel::Voyage3),
+        Err(VoyageError::EmptyInput(1))
+    ));
+}
+
+#[test]
+fn test_embeddings_context_length_without_truncation() {
+    // Four bytes per estimated token: 16_001 tokens, over voyage-law-2's 16k context.
+    let long = "abcd".repeat(16_001);
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(vec!["short".to_string(), long.clone()])
+        .model(EmbeddingModel::VoyageLaw2)
+        .truncation(false)
+        .build();
+    match request.map_err(VoyageError::from) {
+        Err(VoyageError::InputTooLong {
+            index,
+            tokens,
+            limit,
+        }) => assert_eq!((index, tokens, limit), (1, 16_001, 16_000)),
+        other => panic!("expected InputTooLong, got {:?}", other),
+    }
+
+    // With truncation (the API default) the text is cut to the context length.
+    assert!(build(vec![long], EmbeddingModel::VoyageLaw2).is_ok());
+}
+
+#[test]
+fn test_embeddings_total_tokens() {
+    // Eight texts at the 16k context length make 128k tokens, over the 120k request limit.
+    let texts = vec!["abcd".repeat(16_000); 8];
+    match build(texts, EmbeddingModel::VoyageFinance2) {
+        Err(VoyageError::TokenLimitExceeded(tokens, limit)) => {
+            assert_eq!((tokens, limit), (128_000, 120_000))
+        }
+        other => panic!("expected TokenLimitExceeded, got {:?}", other),
+    }
+    assert!(build(
+        vec!["abcd".repeat(16_000); 7],
+        EmbeddingModel::VoyageFinance2
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_embeddings_token_checks_can_be_turned_off() {
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(vec!["abcd".repeat(16_001); 8])
+        .model(EmbeddingModel::VoyageLaw2)
+        .truncation(false)
+        .check_token_limits(false)
+        .build();
+    assert!(request.is_ok());
+
+    // Checks that do not depend on token estimates still run.
+    let request = EmbeddingsRequestBuilder::new()
+        .documents(vec![String::new()])
+        .model(EmbeddingModel::VoyageLaw2)
+        .check_token_limits(false)
+        .build();
+    assert!(matches!(request, Err(VoyageBuilderError::EmptyInput(0))));
+}
+
+#[test]
+fn test_rerank_query_document_length() {
+    let query = "abcd".repeat(1_000);
+    let documents = vec!["short".to_string(), "abcd".repeat(7_001)];
+
+    // The API truncates by default, so the length only matters with truncation off.
+    let request = RerankRequest::new(
+        query.clone(),
+        documents.clone(),
+        RerankModel::Rerank2Lite,
+        None,
+    )
+    .expect("truncated by the API");
+    assert_eq!(request.truncation, None);
+
+    let result = request.clone().with_truncation(false);
+    match &result {
+        Err(ValidationError::QueryDocumentTokenLimitExceeded {
+            index,
+            tokens,
+            limit,
+        }) => assert_eq!((*index, *tokens, *limit), (1, 8_001, 8_000)),
+        other => panic!("expected QueryDocumentTokenLimitExceeded, got {:?}", other),
+    }
+    assert!(matches!(
+        VoyageError::from(result.unwrap_err()),
+        VoyageError::QueryDocumentTokenLimitExceeded(8_001, 8_000)
+    ));
+    assert!(request.with_truncation(true).is_ok());
+
+    let request = RerankRequest::new(query, documents, RerankModel::Rerank2, None)
+        .and_then(|r| r.with_truncation(false));
+    assert!(request.is_ok());
+}
+
+#[test]
+fn test_rerank_truncation_round_trips() {
+    let mut body = serde_json::json!({
+        "query": "abcd".repeat(1_000),
+        "documents": ["abcd".repeat(7_001)],
+        "model": "rerank-2-lite",
+    });
+    let request: RerankRequest = serde_json::from_value(body.clone()).expect("truncated");
+    assert!(serde_json::to_value(&request)
+        .expect("serializes")
+        .get("truncation")
+        .is_none());
+
+    body["truncation"] = false.into();
+    assert!(serde_json::from_value::<RerankRequest>(body).is_err());
+}
+
+#[test]
+fn test_rerank_empty_query_and_documents() {
+    let documents = vec!["rust".to_string(), String::new()];
+
+    let result = RerankRequest::new(String::new(), documents.clone(), RerankModel::Rerank2, None);
+    assert_eq!(result.unwrap_err(), ValidationError::EmptyQuery);
+
+    let result = RerankRequest::new("query".to_string(), documents, RerankModel::Rerank2, None);
+    assert_eq!(
+        result.clone().unwrap_err(),
+        ValidationError::EmptyDocument { index: 1 }
+    );
+    assert!(matches!(
+        VoyageError::from(result.unwrap_err()),
+        VoyageError::EmptyInput(1)
+    ));
+}